@@ -0,0 +1,44 @@
+//! Host-side helpers for Rust clients, available behind the `client` feature
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::states::{Escrow, ESCROW_SEED_LEN};
+
+/// Derive the address and canonical bump of the escrow `maker` opens with `seed`
+pub fn derive_escrow_pda(
+    maker: &Pubkey,
+    seed: &[u8; ESCROW_SEED_LEN],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    find_escrow_pda(Escrow::PREFIX.as_bytes(), maker, seed, program_id)
+}
+
+/// Derive the address and canonical bump of the escrow `maker` opens with `seed` in
+/// `namespace`, one of the `Escrow::NAMESPACE_*` values
+pub fn derive_namespaced_escrow_pda(
+    namespace: u8,
+    maker: &Pubkey,
+    seed: &[u8; ESCROW_SEED_LEN],
+    program_id: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let prefix = Escrow::namespace_prefix(namespace)?;
+    Ok(find_escrow_pda(prefix, maker, seed, program_id))
+}
+
+fn find_escrow_pda(
+    prefix: &[u8],
+    maker: &Pubkey,
+    seed: &[u8; ESCROW_SEED_LEN],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    let (pda, bump) = solana_pubkey::Pubkey::find_program_address(
+        &[prefix, maker, seed],
+        &solana_pubkey::Pubkey::new_from_array(*program_id),
+    );
+    (pda.to_bytes(), bump)
+}
+
+/// Decode a fetched escrow account's data
+pub fn fetch_escrow(account_data: &[u8]) -> Result<Escrow, ProgramError> {
+    Escrow::unpack(account_data)
+}
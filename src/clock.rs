@@ -0,0 +1,37 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_pubkey::pubkey;
+
+use crate::error::sysvar_unavailable;
+
+/// Clock sysvar
+pub const CLOCK_SYSVAR_ID: Pubkey = pubkey!("SysvarC1ock11111111111111111111111111111111");
+
+/// Offset of `unix_timestamp` in the clock sysvar's data
+const UNIX_TIMESTAMP_OFFSET: usize = 32;
+
+/// Current Unix timestamp. Integrators that pass the clock sysvar among `accounts` have it
+/// read from that account; otherwise it comes from `Clock::get()`.
+pub fn current_time(accounts: &[AccountInfo]) -> Result<u64, ProgramError> {
+    let Some(clock_account) = accounts
+        .iter()
+        .find(|account| account.key() == &CLOCK_SYSVAR_ID)
+    else {
+        return Ok(Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64);
+    };
+
+    let data = clock_account.try_borrow_data()?;
+    if data.len() != core::mem::size_of::<Clock>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let unix_timestamp = i64::from_le_bytes(
+        data[UNIX_TIMESTAMP_OFFSET..UNIX_TIMESTAMP_OFFSET + 8]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?,
+    );
+    Ok(unix_timestamp as u64)
+}
@@ -0,0 +1,30 @@
+use pinocchio::{account_info::AccountInfo, instruction::Signer, pubkey::Pubkey, ProgramResult};
+
+use crate::token::CloseAccount;
+
+/// Close a program-owned account, moving all of its lamports to `destination`.
+/// The runtime sees a zero-lamport, zero-length, system-owned account afterwards, so the
+/// same address can be created again within a later transaction.
+pub fn close_escrow_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    *destination.try_borrow_mut_lamports()? += account.lamports();
+    *account.try_borrow_mut_lamports()? = 0;
+    account.close()
+}
+
+/// Close an emptied token account of `token_program` whose authority is a PDA, returning its
+/// rent to `destination`
+pub fn close_token_account(
+    token_program: &Pubkey,
+    token_account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &AccountInfo,
+    signer: &Signer,
+) -> ProgramResult {
+    CloseAccount {
+        token_program,
+        account: token_account,
+        destination,
+        authority,
+    }
+    .invoke_signed(core::slice::from_ref(signer))
+}
@@ -1,21 +1,202 @@
-use pinocchio::program_error::ProgramError;
+use pinocchio::{msg, program_error::ProgramError};
 
+/// Custom program error codes. Discriminants are part of the client interface:
+/// never renumber or reuse them, only append new variants.
+#[repr(u32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EscrowErrorCode {
-    InvalidMaker,
-    EscrowAlreadyExists,
-    TokenAccountAlreadyExists,
-    PdaMismatch,
-    InvalidTokenOwner,
-    InvalidMakerTokenAccount,
-    InvalidTokenMint,
-    MintMismatch,
-    InvalidEscrowType,
-    InsufficientFunds,
+    InvalidMaker = 6000,
+    EscrowAlreadyExists = 6001,
+    TokenAccountAlreadyExists = 6002,
+    PdaMismatch = 6003,
+    InvalidTokenOwner = 6004,
+    InvalidMakerTokenAccount = 6005,
+    InvalidTokenMint = 6006,
+    MintMismatch = 6007,
+    InvalidEscrowType = 6008,
+    InsufficientFunds = 6009,
+    CooldownActive = 6010,
+    FrozenAccount = 6011,
+    ParamsHashMismatch = 6012,
+    InvalidProceedsVault = 6013,
+    DuplicateAccount = 6014,
+    SysvarUnavailable = 6015,
+    Unauthorized = 6016,
+    MintNotAllowed = 6017,
+    InvalidDiscount = 6018,
+    InvalidAuthorization = 6019,
+    ProgramPaused = 6020,
+    PriceOutOfBounds = 6021,
+    TokenBChargeMismatch = 6022,
+    NoMatchingEscrow = 6023,
+    InvalidTokenProgram = 6024,
+    EscrowStillActive = 6025,
+    InvalidPriceRange = 6026,
+    InvalidRefundAccount = 6027,
+    LimitPriceExceeded = 6028,
+    FillsDisabled = 6029,
+    InvalidDuration = 6030,
+    EscrowNotEmpty = 6031,
+    ProceedsFloorNotMet = 6032,
+    StaleOraclePrice = 6033,
+    EscrowExpired = 6034,
+    InvalidEscrowVault = 6035,
+    InvalidAuctionParams = 6036,
+    BidTooLow = 6037,
+    NoBids = 6038,
+    InvalidFeeAccount = 6039,
+    EscrowNotActive = 6040,
+    TakerNotAllowed = 6041,
+    InvalidDepositRatio = 6042,
+    InvalidEscrowOwner = 6043,
+    ZeroAmount = 6044,
+    ArithmeticOverflow = 6045,
+    IdenticalMints = 6046,
+    MakerCannotTake = 6047,
+    TakerInsufficientTokenA = 6048,
+    TakerInsufficientTokenB = 6049,
+    ExceedsEscrowBalance = 6050,
+    DutchUnderpayment = 6051,
+    PartialFillNotAllowed = 6052,
+    InvalidOracleFeed = 6053,
+}
+
+impl EscrowErrorCode {
+    /// Human-readable description, logged whenever the error is raised
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidMaker => "Maker or taker account is invalid or did not sign",
+            Self::EscrowAlreadyExists => "Escrow account already exists",
+            Self::TokenAccountAlreadyExists => "Token account already exists",
+            Self::PdaMismatch => "Account does not match the expected program address",
+            Self::InvalidTokenOwner => "Token account or mint has the wrong owner",
+            Self::InvalidMakerTokenAccount => "Maker token account is missing or invalid",
+            Self::InvalidTokenMint => "Token account holds the wrong mint",
+            Self::MintMismatch => "Escrows or accounts trade different mints",
+            Self::InvalidEscrowType => "Operation is not supported for this escrow type",
+            Self::InsufficientFunds => "Insufficient funds",
+            Self::CooldownActive => "Fill cooldown has not elapsed",
+            Self::FrozenAccount => "Token account is frozen",
+            Self::ParamsHashMismatch => "Take parameters do not match the committed hash",
+            Self::InvalidProceedsVault => "Token B must be paid to the escrow's proceeds vault",
+            Self::DuplicateAccount => "The same account was passed for two roles",
+            Self::SysvarUnavailable => "Sysvar is unavailable",
+            Self::Unauthorized => "Signer is not the config admin",
+            Self::MintNotAllowed => "Token B mint is not on the allowlist",
+            Self::InvalidDiscount => "Volume discount exceeds 100%",
+            Self::InvalidAuthorization => "Maker authorization signature is missing or invalid",
+            Self::ProgramPaused => "Takes are paused",
+            Self::PriceOutOfBounds => "Price is outside its allowed range",
+            Self::TokenBChargeMismatch => "Taker was not debited the expected token B amount",
+            Self::NoMatchingEscrow => "No escrow can fill the request",
+            Self::InvalidTokenProgram => "Token accounts and mint use different token programs",
+            Self::EscrowStillActive => "Escrow is still open for takes",
+            Self::InvalidPriceRange => "Start price is zero or below the end price",
+            Self::InvalidRefundAccount => "Refunds must go to the escrow's refund account",
+            Self::LimitPriceExceeded => "Fill price exceeds the taker's limit",
+            Self::FillsDisabled => "Fills are disabled on this escrow",
+            Self::InvalidDuration => "Duration or deadline is invalid",
+            Self::EscrowNotEmpty => "Escrow still holds token A",
+            Self::ProceedsFloorNotMet => "Proceeds floor has not been met",
+            Self::StaleOraclePrice => "Oracle price is too old",
+            Self::EscrowExpired => "Escrow has expired",
+            Self::InvalidEscrowVault => {
+                "Escrow token account is not the escrow's vault or holds the wrong mint"
+            }
+            Self::InvalidAuctionParams => "Auction parameters are invalid",
+            Self::BidTooLow => "Bid must exceed the highest bid and meet the reserve price",
+            Self::NoBids => "Auction has no bids to settle",
+            Self::InvalidFeeAccount => "Fee collector is not owned by the escrow's fee recipient",
+            Self::EscrowNotActive => "Escrow has already been completed or cancelled",
+            Self::TakerNotAllowed => "Escrow is reserved for another taker",
+            Self::InvalidDepositRatio => "Deposit must add token B in proportion to token A",
+            Self::InvalidEscrowOwner => "Escrow account is not owned by this program",
+            Self::ZeroAmount => "Escrow amounts must be nonzero",
+            Self::ArithmeticOverflow => "Token amount calculation overflowed",
+            Self::IdenticalMints => "Token A and token B must be different mints",
+            Self::MakerCannotTake => "Maker cannot take their own escrow",
+            Self::TakerInsufficientTokenA => "Taker holds too little token A",
+            Self::TakerInsufficientTokenB => "Taker holds too little token B for the take",
+            Self::ExceedsEscrowBalance => "Take exceeds the token A left in the escrow",
+            Self::DutchUnderpayment => "Taker's maximum is below the Dutch auction's current price",
+            Self::PartialFillNotAllowed => "All-or-nothing escrow must be taken in full",
+            Self::InvalidOracleFeed => "Price feed is not the escrow's oracle feed",
+        }
+    }
 }
 
 impl From<EscrowErrorCode> for ProgramError {
     fn from(e: EscrowErrorCode) -> Self {
+        msg!("Error {}: {}", e as u32, e.as_str());
         ProgramError::Custom(e as u32)
     }
 }
+
+/// Map a failed sysvar syscall to `SysvarUnavailable` so operators can tell
+/// runtime issues apart from validation failures
+pub fn sysvar_unavailable(err: ProgramError) -> ProgramError {
+    msg!("Sysvar unavailable: {:?}", err);
+    EscrowErrorCode::SysvarUnavailable.into()
+}
+
+/// Name of the escrow error behind a `Custom` program error code
+#[cfg(feature = "client")]
+pub fn code_to_str(code: u32) -> &'static str {
+    match code {
+        6000 => "InvalidMaker",
+        6001 => "EscrowAlreadyExists",
+        6002 => "TokenAccountAlreadyExists",
+        6003 => "PdaMismatch",
+        6004 => "InvalidTokenOwner",
+        6005 => "InvalidMakerTokenAccount",
+        6006 => "InvalidTokenMint",
+        6007 => "MintMismatch",
+        6008 => "InvalidEscrowType",
+        6009 => "InsufficientFunds",
+        6010 => "CooldownActive",
+        6011 => "FrozenAccount",
+        6012 => "ParamsHashMismatch",
+        6013 => "InvalidProceedsVault",
+        6014 => "DuplicateAccount",
+        6015 => "SysvarUnavailable",
+        6016 => "Unauthorized",
+        6017 => "MintNotAllowed",
+        6018 => "InvalidDiscount",
+        6019 => "InvalidAuthorization",
+        6020 => "ProgramPaused",
+        6021 => "PriceOutOfBounds",
+        6022 => "TokenBChargeMismatch",
+        6023 => "NoMatchingEscrow",
+        6024 => "InvalidTokenProgram",
+        6025 => "EscrowStillActive",
+        6026 => "InvalidPriceRange",
+        6027 => "InvalidRefundAccount",
+        6028 => "LimitPriceExceeded",
+        6029 => "FillsDisabled",
+        6030 => "InvalidDuration",
+        6031 => "EscrowNotEmpty",
+        6032 => "ProceedsFloorNotMet",
+        6033 => "StaleOraclePrice",
+        6034 => "EscrowExpired",
+        6035 => "InvalidEscrowVault",
+        6036 => "InvalidAuctionParams",
+        6037 => "BidTooLow",
+        6038 => "NoBids",
+        6039 => "InvalidFeeAccount",
+        6040 => "EscrowNotActive",
+        6041 => "TakerNotAllowed",
+        6042 => "InvalidDepositRatio",
+        6043 => "InvalidEscrowOwner",
+        6044 => "ZeroAmount",
+        6045 => "ArithmeticOverflow",
+        6046 => "IdenticalMints",
+        6047 => "MakerCannotTake",
+        6048 => "TakerInsufficientTokenA",
+        6049 => "TakerInsufficientTokenB",
+        6050 => "ExceedsEscrowBalance",
+        6051 => "DutchUnderpayment",
+        6052 => "PartialFillNotAllowed",
+        6053 => "InvalidOracleFeed",
+        _ => "Unknown",
+    }
+}
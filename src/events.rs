@@ -0,0 +1,139 @@
+use pinocchio::{msg, pubkey::Pubkey};
+
+use crate::states::{EscrowType, ESCROW_SEED_LEN};
+
+/// Prefix of the log line carrying a base64-encoded `EscrowEvent`
+pub const EVENT_LOG_PREFIX: &str = "EVENT ";
+
+/// First byte of every event, identifying what happened
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowEventKind {
+    Made = 0xE1,
+    Taken = 0xE2,
+}
+
+/// Structured record of an escrow being made or taken, logged for indexers as
+/// `EVENT <base64>`. The encoded bytes are:
+///
+/// | offset | size | field                                              |
+/// |--------|------|----------------------------------------------------|
+/// | 0      | 1    | kind discriminator, see `EscrowEventKind`          |
+/// | 1      | 1    | escrow type                                        |
+/// | 2      | 32   | maker                                              |
+/// | 34     | 8    | seed                                               |
+/// | 42     | 32   | token A mint                                       |
+/// | 74     | 32   | token B mint                                       |
+/// | 106    | 8    | token A amount, little-endian `u64`                |
+/// | 114    | 8    | token B amount, little-endian `u64`                |
+///
+/// Made events carry the escrowed lot and its ask; taken events carry the token A sent to the
+/// taker and the token B the maker was paid by that take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscrowEvent {
+    pub kind: EscrowEventKind,
+    pub escrow_type: EscrowType,
+    pub maker: Pubkey,
+    pub seed: [u8; ESCROW_SEED_LEN],
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+impl EscrowEvent {
+    pub const LEN: usize = 1 + 1 + 32 + ESCROW_SEED_LEN + 32 + 32 + 8 + 8;
+    pub const ENCODED_LEN: usize = Self::LEN.div_ceil(3) * 4;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = self.kind as u8;
+        data[1] = self.escrow_type as u8;
+        data[2..34].copy_from_slice(&self.maker);
+        data[34..42].copy_from_slice(&self.seed);
+        data[42..74].copy_from_slice(&self.token_a_mint);
+        data[74..106].copy_from_slice(&self.token_b_mint);
+        data[106..114].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[114..122].copy_from_slice(&self.token_b_amount.to_le_bytes());
+        data
+    }
+
+    /// The packed event as padded standard base64
+    pub fn encode(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut encoded = [0u8; Self::ENCODED_LEN];
+        encode_base64(&self.pack(), &mut encoded);
+        encoded
+    }
+
+    /// Log the event as `EVENT <base64>`
+    pub fn emit(&self) {
+        let encoded = self.encode();
+        // Base64 output is always ASCII
+        let encoded = core::str::from_utf8(&encoded).unwrap();
+        msg!("{}{}", EVENT_LOG_PREFIX, encoded);
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `input` as padded standard base64 into `output`, which must hold
+/// `input.len().div_ceil(3) * 4` bytes
+fn encode_base64(input: &[u8], output: &mut [u8]) {
+    for (chunk, out) in input.chunks(3).zip(output.chunks_exact_mut(4)) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = if i <= chunk.len() {
+                BASE64_ALPHABET[(triple >> (18 - 6 * i) & 0x3F) as usize]
+            } else {
+                b'='
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(input: &[u8]) -> String {
+        let mut output = vec![0u8; input.len().div_ceil(3) * 4];
+        encode_base64(input, &mut output);
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn base64_matches_standard_padding() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"M"), "TQ==");
+        assert_eq!(encode(b"Ma"), "TWE=");
+        assert_eq!(encode(b"Man"), "TWFu");
+        assert_eq!(encode(&[0xFB, 0xFF]), "+/8=");
+    }
+
+    #[test]
+    fn event_layout_is_stable() {
+        let event = EscrowEvent {
+            kind: EscrowEventKind::Taken,
+            escrow_type: EscrowType::Partial,
+            maker: [1; 32],
+            seed: 7u64.to_le_bytes(),
+            token_a_mint: [2; 32],
+            token_b_mint: [3; 32],
+            token_a_amount: 250,
+            token_b_amount: 500,
+        };
+        let data = event.pack();
+        assert_eq!(data.len(), 122);
+        assert_eq!(&data[..2], &[0xE2, 1]);
+        assert_eq!(&data[34..42], &7u64.to_le_bytes());
+        assert_eq!(&data[106..114], &250u64.to_le_bytes());
+        assert_eq!(&data[114..], &500u64.to_le_bytes());
+        assert_eq!(event.encode().len(), EscrowEvent::ENCODED_LEN);
+    }
+}
@@ -0,0 +1,231 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    close::{close_escrow_account, close_token_account},
+    error::{sysvar_unavailable, EscrowErrorCode},
+    states::{try_from_account_info_mut, Escrow, ESCROW_SEED_LEN},
+    token::{token_account_unchecked, Transfer as TokenTransfer},
+};
+
+/// Maximum number of escrows closed by a single batch cancel instruction
+pub const MAX_BATCH_CANCEL_ESCROWS: usize = 8;
+
+pub fn cancel_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+    check_proceeds_floor(escrow)?;
+    escrow.transition(Escrow::STATE_CANCELLED)?;
+
+    refund_and_close_escrow(
+        escrow,
+        escrow_account,
+        escrow_token_a_ata,
+        maker_account,
+        maker_token_a_ata,
+    )
+}
+
+/// Close a drained escrow that a take left open because stray tokens were sent to its
+/// vault. The stray balance is refunded as on cancel; a still-funded escrow must be
+/// cancelled instead.
+pub fn close_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if escrow.token_a_amount != 0 {
+        return Err(EscrowErrorCode::EscrowNotEmpty.into());
+    }
+
+    refund_and_close_escrow(
+        escrow,
+        escrow_account,
+        escrow_token_a_ata,
+        maker_account,
+        maker_token_a_ata,
+    )
+}
+
+/// Cancel several of the signing maker's escrows in one instruction.
+/// Data is a list of `ESCROW_SEED_LEN`-byte seeds; accounts after the token program are the
+/// (escrow, escrow token A account) pair for each seed. Escrows that no longer
+/// exist are skipped.
+pub fn batch_cancel(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, _token_program, escrow_accounts @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let seeds = instruction_data.chunks_exact(ESCROW_SEED_LEN);
+    if instruction_data.is_empty() || !seeds.remainder().is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let seed_count = seeds.len();
+    if seed_count > MAX_BATCH_CANCEL_ESCROWS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if escrow_accounts.len() < seed_count * 2 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for (seed, pair) in seeds.zip(escrow_accounts.chunks_exact(2)) {
+        let [escrow_account, escrow_token_a_ata] = pair else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if escrow_account.data_is_empty() {
+            continue;
+        }
+
+        // The stored seed must match so the accounts line up with the requested escrows
+        let escrow = load_maker_escrow(maker_account, escrow_account)?;
+        if escrow.seed != seed {
+            return Err(EscrowErrorCode::PdaMismatch.into());
+        }
+        check_proceeds_floor(escrow)?;
+        escrow.transition(Escrow::STATE_CANCELLED)?;
+
+        refund_and_close_escrow(
+            escrow,
+            escrow_account,
+            escrow_token_a_ata,
+            maker_account,
+            maker_token_a_ata,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Return all of an escrow's remaining token A to its maker (or designated refund account)
+/// and close its accounts
+pub(crate) fn refund_and_close_escrow(
+    escrow: &Escrow,
+    escrow_account: &AccountInfo,
+    escrow_token_a_ata: &AccountInfo,
+    maker_account: &AccountInfo,
+    maker_token_a_ata: &AccountInfo,
+) -> ProgramResult {
+    // A bidder's token B stays locked until the auction settles
+    if escrow.has_bids() {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    }
+
+    // Refunds go to the refund account the maker designated at make, if any
+    if escrow.refund_account != [0; 32] && maker_token_a_ata.key() != &escrow.refund_account {
+        return Err(EscrowErrorCode::InvalidRefundAccount.into());
+    }
+    let refund_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(maker_token_a_ata) }?;
+    if refund_token_a_account.mint() != &escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    // Only the vault the make funded is refunded and closed
+    if escrow_token_a_ata.key() != &escrow.vault {
+        return Err(EscrowErrorCode::InvalidEscrowVault.into());
+    }
+
+    let escrow_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(escrow_token_a_ata) }?;
+    if escrow_token_a_account.mint() != &escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    let refund_amount = escrow_token_a_account.amount();
+
+    let bump_array = [escrow.bump];
+    let seed = [
+        Seed::from(escrow.prefix()),
+        Seed::from(maker_account.key()),
+        Seed::from(&escrow.seed),
+        Seed::from(&bump_array),
+    ];
+    let signer = Signer::from(&seed);
+
+    // Return all remaining token A to the maker
+    TokenTransfer {
+        token_program: &escrow.token_program,
+        from: escrow_token_a_ata,
+        to: maker_token_a_ata,
+        authority: escrow_account,
+        amount: refund_amount,
+    }
+    .invoke_signed(core::slice::from_ref(&signer))?;
+
+    close_token_account(
+        &escrow.token_program,
+        escrow_token_a_ata,
+        maker_account,
+        escrow_account,
+        &signer,
+    )?;
+    close_escrow_account(escrow_account, maker_account)
+}
+
+/// Reject returning token A to the maker while the escrow's proceeds floor still locks it
+pub(crate) fn check_proceeds_floor(escrow: &Escrow) -> ProgramResult {
+    if escrow.proceeds_floor == 0 {
+        return Ok(());
+    }
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    if escrow.is_floor_locked(current_time) {
+        return Err(EscrowErrorCode::ProceedsFloorNotMet.into());
+    }
+    Ok(())
+}
+
+/// Load an escrow for a maker-signed instruction, checking the signer created it
+// The escrow lives in the account's data buffer, which `AccountInfo` hands out mutably through a
+// shared reference just like `try_from_account_info_mut`; it isn't borrowed from `maker_account`
+#[allow(clippy::mut_from_ref)]
+pub(crate) fn load_maker_escrow<'a>(
+    maker_account: &AccountInfo,
+    escrow_account: &'a AccountInfo,
+) -> Result<&'a mut Escrow, ProgramError> {
+    if !maker_account.is_signer() {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    let escrow = unsafe { try_from_account_info_mut::<Escrow>(escrow_account) }?;
+    if &escrow.maker_pubkey != maker_account.key() {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    Escrow::verify_escrow_address(
+        escrow_account.key(),
+        escrow.prefix(),
+        maker_account.key(),
+        &escrow.bump,
+        &escrow.seed,
+    )?;
+
+    Ok(escrow)
+}
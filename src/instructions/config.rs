@@ -0,0 +1,189 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::pubkey;
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    states::{try_from_account_info_mut, Config, DataLen, MAX_ALLOWED_TOKEN_B_MINTS},
+};
+
+/// Upgradeable BPF loader, which owns the program's ProgramData account
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// ProgramData layout: `u32` state tag, `u64` deploy slot, then the upgrade authority as an
+/// `Option<Pubkey>` (one tag byte and the key)
+const PROGRAM_DATA_STATE: u32 = 3;
+const UPGRADE_AUTHORITY_OFFSET: usize = 4 + 8;
+
+/// Create or update the program config. Only the program's upgrade authority may create it,
+/// becoming its admin, so nobody can front-run the deployer; afterwards only the admin may
+/// change it. Creating the config takes the program's ProgramData account after the system
+/// program.
+pub fn set_config(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [admin_account, config_account, _system_program, remaining @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !admin_account.is_signer() {
+        return Err(EscrowErrorCode::Unauthorized.into());
+    }
+
+    let ix_data = SetConfigIx::unpack(instruction_data)?;
+
+    if config_account.key() != &Config::ADDRESS {
+        return Err(EscrowErrorCode::PdaMismatch.into());
+    }
+
+    if config_account.data_is_empty() {
+        let program_data = remaining
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_upgrade_authority(program_data, admin_account.key())?;
+
+        let bump_array = [Config::BUMP];
+        let seed = [
+            Seed::from(Config::PREFIX.as_bytes()),
+            Seed::from(&bump_array),
+        ];
+        let signer = Signer::from(&seed);
+
+        CreateAccount {
+            from: admin_account,
+            to: config_account,
+            lamports: Rent::get()
+                .map_err(sysvar_unavailable)?
+                .minimum_balance(Config::LEN),
+            space: Config::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[signer])?;
+
+        let config = unsafe { try_from_account_info_mut::<Config>(config_account) }?;
+        config.admin = *admin_account.key();
+        config.bump = Config::BUMP;
+    }
+
+    let config = unsafe { try_from_account_info_mut::<Config>(config_account) }?;
+    if &config.admin != admin_account.key() {
+        return Err(EscrowErrorCode::Unauthorized.into());
+    }
+
+    config.allowlist_enabled = ix_data.allowlist_enabled as u8;
+    config.allowed_token_b_mint_count = ix_data.allowed_token_b_mint_count;
+    config.allowed_token_b_mints = ix_data.allowed_token_b_mints;
+    config.takes_paused = ix_data.takes_paused as u8;
+    config.max_auction_duration = ix_data.max_auction_duration;
+
+    Ok(())
+}
+
+/// Reject `admin` unless it is the upgrade authority recorded in the program's ProgramData
+fn check_upgrade_authority(program_data: &AccountInfo, admin: &Pubkey) -> ProgramResult {
+    let (program_data_key, _) =
+        pubkey::find_program_address(&[&crate::ID], &BPF_LOADER_UPGRADEABLE_ID);
+    if program_data.key() != &program_data_key
+        || unsafe { program_data.owner() } != &BPF_LOADER_UPGRADEABLE_ID
+    {
+        return Err(EscrowErrorCode::PdaMismatch.into());
+    }
+
+    let data = program_data.try_borrow_data()?;
+    let state = data
+        .get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes);
+    let authority = data.get(UPGRADE_AUTHORITY_OFFSET..UPGRADE_AUTHORITY_OFFSET + 33);
+    match (state, authority) {
+        (Some(PROGRAM_DATA_STATE), Some([1, key @ ..])) if key == admin => Ok(()),
+        _ => Err(EscrowErrorCode::Unauthorized.into()),
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SetConfigIx {
+    pub allowlist_enabled: bool,
+    pub allowed_token_b_mint_count: u8,
+    pub allowed_token_b_mints: [[u8; 32]; MAX_ALLOWED_TOKEN_B_MINTS],
+    pub takes_paused: bool,
+    pub max_auction_duration: u64, // Longest Dutch auction allowed, in seconds (0 = unlimited)
+}
+
+impl SetConfigIx {
+    pub const LEN: usize = 1 + 1 + 32 * MAX_ALLOWED_TOKEN_B_MINTS + 1 + 8;
+
+    const TAKES_PAUSED_OFFSET: usize = 2 + 32 * MAX_ALLOWED_TOKEN_B_MINTS;
+
+    /// Build a config update enabling the allowlist with `mints`
+    pub fn with_allowlist(mints: &[[u8; 32]]) -> Result<Self, ProgramError> {
+        if mints.len() > MAX_ALLOWED_TOKEN_B_MINTS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let mut allowed_token_b_mints = [[0; 32]; MAX_ALLOWED_TOKEN_B_MINTS];
+        allowed_token_b_mints[..mints.len()].copy_from_slice(mints);
+        Ok(Self {
+            allowlist_enabled: true,
+            allowed_token_b_mint_count: mints.len() as u8,
+            allowed_token_b_mints,
+            takes_paused: false,
+            max_auction_duration: 0,
+        })
+    }
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = self.allowlist_enabled as u8;
+        data[1] = self.allowed_token_b_mint_count;
+        for (chunk, mint) in data[2..Self::TAKES_PAUSED_OFFSET]
+            .chunks_exact_mut(32)
+            .zip(self.allowed_token_b_mints.iter())
+        {
+            chunk.copy_from_slice(mint);
+        }
+        data[Self::TAKES_PAUSED_OFFSET] = self.takes_paused as u8;
+        data[Self::TAKES_PAUSED_OFFSET + 1..]
+            .copy_from_slice(&self.max_auction_duration.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let allowed_token_b_mint_count = data[1];
+        if allowed_token_b_mint_count as usize > MAX_ALLOWED_TOKEN_B_MINTS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut allowed_token_b_mints = [[0; 32]; MAX_ALLOWED_TOKEN_B_MINTS];
+        for (mint, chunk) in allowed_token_b_mints
+            .iter_mut()
+            .zip(data[2..Self::TAKES_PAUSED_OFFSET].chunks_exact(32))
+        {
+            mint.copy_from_slice(chunk);
+        }
+
+        Ok(Self {
+            allowlist_enabled: data[0] != 0,
+            allowed_token_b_mint_count,
+            allowed_token_b_mints,
+            takes_paused: data[Self::TAKES_PAUSED_OFFSET] != 0,
+            max_auction_duration: u64::from_le_bytes(
+                data[Self::TAKES_PAUSED_OFFSET + 1..].try_into().unwrap(),
+            ),
+        })
+    }
+}
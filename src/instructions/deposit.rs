@@ -0,0 +1,100 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::{check_escrow_vault, load_maker_escrow},
+    states::{Escrow, EscrowType},
+    token::Transfer as TokenTransfer,
+};
+
+/// Top up an open Simple or Partial escrow with more token A from the maker.
+/// The token B ask must grow in the same proportion, so a deposit never changes the price;
+/// makers reprice with `update_escrow`.
+pub fn deposit_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = DepositEscrowIx::unpack(instruction_data)?;
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if !matches!(escrow.escrow_type, EscrowType::Simple | EscrowType::Partial) {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+    if escrow.state != Escrow::STATE_ACTIVE || escrow.token_a_amount == 0 {
+        return Err(EscrowErrorCode::EscrowNotActive.into());
+    }
+    if ix_data.token_a_amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // token_b / token_a must equal the escrow's remaining ask / remaining token A
+    if ix_data.token_b_amount as u128 * escrow.token_a_amount as u128
+        != ix_data.token_a_amount as u128 * escrow.token_b_amount as u128
+    {
+        return Err(EscrowErrorCode::InvalidDepositRatio.into());
+    }
+
+    let add = |total: u64, amount: u64| {
+        total
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    };
+    let token_a_amount = add(escrow.token_a_amount, ix_data.token_a_amount)?;
+    let token_b_amount = add(escrow.token_b_amount, ix_data.token_b_amount)?;
+    let original_token_a_amount = add(escrow.original_token_a_amount, ix_data.token_a_amount)?;
+
+    check_escrow_vault(escrow_token_a_ata, escrow_account, &escrow.token_a_mint)?;
+
+    TokenTransfer {
+        token_program: &escrow.token_program,
+        from: maker_token_a_ata,
+        to: escrow_token_a_ata,
+        authority: maker_account,
+        amount: ix_data.token_a_amount,
+    }
+    .invoke()?;
+
+    escrow.token_a_amount = token_a_amount;
+    escrow.token_b_amount = token_b_amount;
+    escrow.original_token_a_amount = original_token_a_amount;
+
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositEscrowIx {
+    pub token_a_amount: u64, // Token A added to the escrow
+    pub token_b_amount: u64, // Token B added to the ask, in proportion to token A
+}
+
+impl DepositEscrowIx {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..8].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[8..16].copy_from_slice(&self.token_b_amount.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            token_a_amount: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            token_b_amount: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        })
+    }
+}
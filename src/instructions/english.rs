@@ -0,0 +1,280 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    close::{close_escrow_account, close_token_account},
+    error::{sysvar_unavailable, EscrowErrorCode},
+    events::{EscrowEvent, EscrowEventKind},
+    instructions::check_escrow_vault,
+    states::{try_from_account_info_mut, Escrow, EscrowType},
+    token::{token_account_unchecked, Transfer as TokenTransfer},
+};
+
+/// Bid on an English auction. The bid must strictly exceed the highest bid so far and meet
+/// the reserve price (the escrow's `token_b_amount`). It is held in the escrow's token B
+/// account, and the previous highest bid is refunded to the previous bidder's token account.
+pub fn place_bid(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [bidder_account, bidder_token_b_ata, escrow_account, escrow_token_b_ata, previous_bidder_token_b_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = PlaceBidIx::unpack(instruction_data)?;
+
+    if !bidder_account.is_signer() {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    let escrow = load_english_auction(escrow_account)?;
+    check_escrow_vault(escrow_token_b_ata, escrow_account, &escrow.token_b_mint)?;
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    if current_time >= escrow.auction_end || escrow.is_past_expiry(current_time) {
+        return Err(EscrowErrorCode::EscrowExpired.into());
+    }
+
+    if ix_data.amount <= escrow.highest_bid || ix_data.amount < escrow.token_b_amount {
+        return Err(EscrowErrorCode::BidTooLow.into());
+    }
+
+    let bidder_token_b_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(bidder_token_b_ata) }?;
+    if bidder_token_b_account.mint() != &escrow.token_b_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    if bidder_token_b_account.amount() < ix_data.amount {
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    TokenTransfer {
+        token_program: &pinocchio_token::ID,
+        from: bidder_token_b_ata,
+        to: escrow_token_b_ata,
+        authority: bidder_account,
+        amount: ix_data.amount,
+    }
+    .invoke()?;
+
+    // Outbid funds go back to the wallet that placed them
+    if escrow.has_bids() {
+        let previous_bidder_token_b_account: &TokenAccount =
+            unsafe { TokenAccount::from_account_info_unchecked(previous_bidder_token_b_ata) }?;
+        if previous_bidder_token_b_account.owner() != &escrow.highest_bidder {
+            return Err(EscrowErrorCode::InvalidTokenOwner.into());
+        }
+        if previous_bidder_token_b_account.mint() != &escrow.token_b_mint {
+            return Err(EscrowErrorCode::InvalidTokenMint.into());
+        }
+
+        let bump_array = [escrow.bump];
+        let seed = [
+            Seed::from(escrow.prefix()),
+            Seed::from(&escrow.maker_pubkey),
+            Seed::from(&escrow.seed),
+            Seed::from(&bump_array),
+        ];
+        TokenTransfer {
+            token_program: &pinocchio_token::ID,
+            from: escrow_token_b_ata,
+            to: previous_bidder_token_b_ata,
+            authority: escrow_account,
+            amount: escrow.highest_bid,
+        }
+        .invoke_signed(&[Signer::from(&seed)])?;
+    }
+
+    escrow.highest_bid = ix_data.amount;
+    escrow.highest_bidder = *bidder_account.key();
+
+    Ok(())
+}
+
+/// Settle an English auction once bidding has closed. Anyone may call it: the escrow's
+/// token A goes to the highest bidder, the held token B to the maker (or the proceeds vault),
+/// and the escrow's accounts are closed with their rent returned to the maker.
+pub fn settle_auction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, escrow_token_a_ata, escrow_token_b_ata, maker_account, maker_token_b_ata, winner_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_english_auction(escrow_account)?;
+    if maker_account.key() != &escrow.maker_pubkey {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+    check_escrow_vault(escrow_token_b_ata, escrow_account, &escrow.token_b_mint)?;
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    if current_time < escrow.auction_end {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    }
+    // Unsold lots are refunded to the maker by finalize
+    if !escrow.has_bids() {
+        return Err(EscrowErrorCode::NoBids.into());
+    }
+
+    let winner_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(winner_token_a_ata) }?;
+    if winner_token_a_account.owner() != &escrow.highest_bidder {
+        return Err(EscrowErrorCode::InvalidTokenOwner.into());
+    }
+    if winner_token_a_account.mint() != &escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    if escrow.token_b_vault != [0; 32] && maker_token_b_ata.key() != &escrow.token_b_vault {
+        return Err(EscrowErrorCode::InvalidProceedsVault.into());
+    }
+    let maker_token_b_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(maker_token_b_ata) }?;
+    if maker_token_b_account.mint() != &escrow.token_b_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    // Anyone may settle, so without a proceeds vault the bid must go to an account the maker owns
+    if escrow.token_b_vault == [0; 32] && maker_token_b_account.owner() != &escrow.maker_pubkey {
+        return Err(EscrowErrorCode::InvalidTokenOwner.into());
+    }
+
+    let escrow_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(escrow_token_a_ata) }?;
+    if escrow_token_a_account.mint() != &escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    let escrow_token_b_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(escrow_token_b_ata) }?;
+
+    // Both vaults are emptied so they can be closed; anything sent to them beyond the lot
+    // and the winning bid follows it
+    let token_a_amount = escrow_token_a_account.amount();
+    let token_b_amount = escrow_token_b_account.amount();
+
+    let token_program = escrow.token_program;
+    let bump_array = [escrow.bump];
+    let escrow_seed = escrow.seed;
+    let seed = [
+        Seed::from(escrow.prefix()),
+        Seed::from(maker_account.key()),
+        Seed::from(&escrow_seed),
+        Seed::from(&bump_array),
+    ];
+    let signer = Signer::from(&seed);
+
+    TokenTransfer {
+        token_program: &token_program,
+        from: escrow_token_a_ata,
+        to: winner_token_a_ata,
+        authority: escrow_account,
+        amount: token_a_amount,
+    }
+    .invoke_signed(core::slice::from_ref(&signer))?;
+
+    TokenTransfer {
+        token_program: &pinocchio_token::ID,
+        from: escrow_token_b_ata,
+        to: maker_token_b_ata,
+        authority: escrow_account,
+        amount: token_b_amount,
+    }
+    .invoke_signed(core::slice::from_ref(&signer))?;
+
+    let event = EscrowEvent {
+        kind: EscrowEventKind::Taken,
+        escrow_type: EscrowType::English,
+        maker: escrow.maker_pubkey,
+        seed: escrow_seed,
+        token_a_mint: escrow.token_a_mint,
+        token_b_mint: escrow.token_b_mint,
+        token_a_amount,
+        token_b_amount,
+    };
+    escrow.token_a_amount = 0;
+
+    close_token_account(
+        &token_program,
+        escrow_token_a_ata,
+        maker_account,
+        escrow_account,
+        &signer,
+    )?;
+    close_token_account(
+        &pinocchio_token::ID,
+        escrow_token_b_ata,
+        maker_account,
+        escrow_account,
+        &signer,
+    )?;
+    close_escrow_account(escrow_account, maker_account)?;
+
+    msg!(
+        "ESCROW_TAKEN id={} token_a_amount={}",
+        Escrow::escrow_id(maker_account.key(), &escrow_seed),
+        token_a_amount
+    );
+    event.emit();
+
+    Ok(())
+}
+
+/// Load an English auction escrow owned by this program at its expected address
+// Borrowed from the account's data buffer like `load_maker_escrow`, not from the `AccountInfo`
+#[allow(clippy::mut_from_ref)]
+fn load_english_auction(escrow_account: &AccountInfo) -> Result<&mut Escrow, ProgramError> {
+    if unsafe { escrow_account.owner() } != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let escrow = unsafe { try_from_account_info_mut::<Escrow>(escrow_account) }?;
+    if escrow.escrow_type != EscrowType::English {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    Escrow::verify_escrow_address(
+        escrow_account.key(),
+        escrow.prefix(),
+        &escrow.maker_pubkey,
+        &escrow.bump,
+        &escrow.seed,
+    )?;
+
+    Ok(escrow)
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaceBidIx {
+    pub amount: u64, // Token B offered for the whole lot
+}
+
+impl PlaceBidIx {
+    pub const LEN: usize = 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        self.amount.to_le_bytes()
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let amount = u64::from_le_bytes(
+            data.try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        );
+        Ok(Self { amount })
+    }
+}
@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    error::EscrowErrorCode,
+    states::{Config, FeeVault},
+    token::Transfer as TokenTransfer,
+};
+
+/// Send the whole balance of a fee vault, a token account owned by the fee vault authority of
+/// its mint, to a token account of the config admin's choosing. Only the config admin may call it.
+pub fn withdraw_fees(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [admin_account, config_account, fee_vault_authority, fee_vault, destination, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !admin_account.is_signer() {
+        return Err(EscrowErrorCode::Unauthorized.into());
+    }
+    match Config::load(config_account)? {
+        Some(config) if &config.admin == admin_account.key() => {}
+        _ => return Err(EscrowErrorCode::Unauthorized.into()),
+    }
+
+    let fee_vault_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(fee_vault) }?;
+    let mint = *fee_vault_account.mint();
+    let (authority, bump) = FeeVault::find_authority(&mint);
+    if fee_vault_authority.key() != &authority {
+        return Err(EscrowErrorCode::PdaMismatch.into());
+    }
+    if fee_vault_account.owner() != &authority {
+        return Err(EscrowErrorCode::InvalidFeeAccount.into());
+    }
+    let amount = fee_vault_account.amount();
+    if amount == 0 {
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    let destination_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(destination) }?;
+    if destination_account.mint() != &mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    let bump_array = [bump];
+    let seed = [
+        Seed::from(FeeVault::PREFIX.as_bytes()),
+        Seed::from(&mint),
+        Seed::from(&bump_array),
+    ];
+
+    TokenTransfer {
+        token_program: &pinocchio_token::ID,
+        from: fee_vault,
+        to: destination,
+        authority: fee_vault_authority,
+        amount,
+    }
+    .invoke_signed(&[Signer::from(&seed)])
+}
@@ -0,0 +1,40 @@
+use pinocchio::{
+    account_info::AccountInfo, cpi::set_return_data, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::EscrowErrorCode,
+    states::{try_from_account_info, Escrow, EscrowType, FillRecord, FILL_HISTORY_LEN},
+};
+
+/// Writes the recorded partial fills of an escrow to return data.
+/// Layout: one count byte followed by `count` packed `FillRecord`s, most recent first.
+pub fn fill_history(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+    if escrow.escrow_type != EscrowType::Partial {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    let mut data = [0u8; 1 + FILL_HISTORY_LEN * FillRecord::LEN];
+    let mut count = 0;
+    for record in escrow.recent_fills() {
+        let offset = 1 + count * FillRecord::LEN;
+        data[offset..offset + FillRecord::LEN].copy_from_slice(&record.pack());
+        count += 1;
+    }
+    data[0] = count as u8;
+
+    set_return_data(&data[..1 + count * FillRecord::LEN]);
+
+    Ok(())
+}
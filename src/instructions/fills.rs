@@ -0,0 +1,33 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{error::EscrowErrorCode, instructions::load_maker_escrow, states::EscrowType};
+
+/// Pause or resume fills on a partial escrow without canceling it.
+/// Data is a single byte: 1 to enable fills, 0 to disable them.
+pub fn set_fills_enabled(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let fills_enabled = match instruction_data {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if escrow.escrow_type != EscrowType::Partial {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    escrow.fills_enabled = fills_enabled as u8;
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    instructions::{check_proceeds_floor, refund_and_close_escrow},
+    states::{try_from_account_info, CloseReason, Escrow},
+    token::token_account_unchecked,
+};
+
+/// Close an escrow that has reached a terminal state. Anyone may call it: a filled
+/// escrow is just closed, an expired one first refunds its token A to the maker.
+/// Either way the `ESCROW_CLOSED` event is logged with the reason.
+pub fn finalize_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, escrow_token_a_ata, maker_account, maker_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_refundable_escrow(escrow_account, maker_account, maker_token_a_ata)?;
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    let reason = if escrow.token_a_amount == 0 {
+        CloseReason::Filled
+    } else if escrow.is_expired(current_time) {
+        CloseReason::Expired
+    } else {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    };
+
+    check_proceeds_floor(escrow)?;
+
+    let escrow_id = Escrow::escrow_id(maker_account.key(), &escrow.seed);
+    refund_and_close_escrow(
+        escrow,
+        escrow_account,
+        escrow_token_a_ata,
+        maker_account,
+        maker_token_a_ata,
+    )?;
+
+    msg!("ESCROW_CLOSED id={} reason={:?}", escrow_id, reason);
+
+    Ok(())
+}
+
+/// Refund an escrow whose `expiry` has passed and close it. Anyone may call it, so cranks
+/// can clear expired offers; the caller only pays the fee, and the token A and rent go back
+/// to the maker (or the refund account designated at make). Escrows without an expiry
+/// can only be closed by their maker or by `finalize_escrow`.
+pub fn reclaim_expired(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, escrow_token_a_ata, maker_account, maker_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_refundable_escrow(escrow_account, maker_account, maker_token_a_ata)?;
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    if !escrow.is_past_expiry(current_time) {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    }
+    // An expiry before the proceeds deadline doesn't release the floor early
+    check_proceeds_floor(escrow)?;
+
+    let escrow_id = Escrow::escrow_id(maker_account.key(), &escrow.seed);
+    refund_and_close_escrow(
+        escrow,
+        escrow_account,
+        escrow_token_a_ata,
+        maker_account,
+        maker_token_a_ata,
+    )?;
+
+    msg!(
+        "ESCROW_CLOSED id={} reason={:?}",
+        escrow_id,
+        CloseReason::Expired
+    );
+
+    Ok(())
+}
+
+/// Load an escrow for an instruction anyone may call, checking it belongs to `maker_account`
+/// and that `maker_token_a_ata` may receive its refund
+fn load_refundable_escrow<'a>(
+    escrow_account: &'a AccountInfo,
+    maker_account: &AccountInfo,
+    maker_token_a_ata: &AccountInfo,
+) -> Result<&'a Escrow, ProgramError> {
+    if unsafe { escrow_account.owner() } != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+    if maker_account.key() != &escrow.maker_pubkey {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    Escrow::verify_escrow_address(
+        escrow_account.key(),
+        escrow.prefix(),
+        maker_account.key(),
+        &escrow.bump,
+        &escrow.seed,
+    )?;
+
+    // Without a designated refund account, refunds may only go to the maker's own token account
+    if escrow.refund_account == [0; 32] {
+        let maker_token_a_account: &TokenAccount =
+            unsafe { token_account_unchecked(maker_token_a_ata) }?;
+        if maker_token_a_account.owner() != maker_account.key()
+            || maker_token_a_account.mint() != &escrow.token_a_mint
+        {
+            return Err(EscrowErrorCode::InvalidMakerTokenAccount.into());
+        }
+    }
+
+    Ok(escrow)
+}
@@ -1,26 +1,35 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    msg,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvars::rent::Rent,
     ProgramResult,
 };
 use pinocchio_system::instructions::CreateAccount;
-use pinocchio_token::{instructions::Transfer as TokenTransfer, state::TokenAccount, ID};
+use pinocchio_token::{state::TokenAccount, ID};
 
 use crate::{
-    error::EscrowErrorCode,
-    states::{DataLen, Escrow, EscrowType},
+    clock::current_time,
+    error::{sysvar_unavailable, EscrowErrorCode},
+    events::{EscrowEvent, EscrowEventKind},
+    states::{
+        Config, DataLen, Escrow, EscrowInit, EscrowType, ESCROW_SEED_LEN, SOL_DECIMALS,
+        SOL_TOKEN_B_MINT,
+    },
+    token::{is_token_program, mint_unchecked, token_account_unchecked, Transfer as TokenTransfer},
 };
 
+/// Longest Dutch auction any maker may open, in seconds, whatever the config allows
+pub const MAX_AUCTION_DURATION: u64 = 365 * 24 * 60 * 60;
+
 pub fn make_escrow(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
 ) -> ProgramResult {
-    use pinocchio::sysvars::{clock::Clock, Sysvar};
-    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, token_a_mint, token_b_mint, _system_program, _rent_sysvar, _remaing @ ..] =
+    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, token_a_mint, token_b_mint, _system_program, _rent_sysvar, config_account, remaining_accounts @ ..] =
         &accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -31,36 +40,252 @@ pub fn make_escrow(
         return Err(EscrowErrorCode::InvalidMaker.into());
     }
 
-    if !escrow_account.data_is_empty() {
-        return Err(EscrowErrorCode::EscrowAlreadyExists.into());
-    }
-
-    if unsafe { token_a_mint.owner() } != &ID || unsafe { token_b_mint.owner() } != &ID {
-        return Err(EscrowErrorCode::InvalidTokenOwner.into());
-    }
     if maker_token_a_ata.data_is_empty() {
         return Err(EscrowErrorCode::InvalidMakerTokenAccount.into());
     }
 
     let maker_token_a_account: &TokenAccount =
-        unsafe { TokenAccount::from_account_info_unchecked(maker_token_a_ata) }?;
+        unsafe { token_account_unchecked(maker_token_a_ata) }?;
     if maker_token_a_account.owner() != maker_account.key() {
         return Err(EscrowErrorCode::InvalidTokenOwner.into());
     }
 
+    check_token_program(token_a_mint, &[maker_token_a_ata, escrow_token_a_ata])?;
+    check_escrow_vault(escrow_token_a_ata, escrow_account, token_a_mint.key())?;
+
     let ix_data = MakeEscrowIx::unpack(_instruction_data)?;
 
-    Escrow::validate_escrow_pda(
+    // Fail with a program error rather than an opaque token program one inside the transfer
+    if maker_token_a_account.amount() < ix_data.token_a_amount {
+        msg!(
+            "Maker holds {} token A but the escrow needs {}",
+            maker_token_a_account.amount(),
+            ix_data.token_a_amount
+        );
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    create_escrow_account(
+        &NewEscrowAccounts {
+            payer: maker_account,
+            maker: maker_account.key(),
+            escrow: escrow_account,
+            escrow_token_a_ata,
+            token_a_mint,
+            token_b_mint,
+            config: config_account,
+            remaining: remaining_accounts,
+        },
+        &ix_data,
+    )?;
+
+    TokenTransfer {
+        token_program: unsafe { token_a_mint.owner() },
+        from: maker_token_a_ata,
+        to: escrow_token_a_ata,
+        authority: maker_account,
+        amount: ix_data.token_a_amount,
+    }
+    .invoke()?;
+
+    Ok(())
+}
+
+/// Reject token accounts owned by a different token program than their mint,
+/// e.g. a classic token account paired with a Token-2022 mint
+pub(crate) fn check_token_program(
+    mint: &AccountInfo,
+    token_accounts: &[&AccountInfo],
+) -> ProgramResult {
+    let token_program = unsafe { mint.owner() };
+    if token_accounts
+        .iter()
+        .any(|account| unsafe { account.owner() } != token_program)
+    {
+        return Err(EscrowErrorCode::InvalidTokenProgram.into());
+    }
+    Ok(())
+}
+
+/// Reject an escrow token account that the escrow PDA doesn't own or that holds a mint other
+/// than `mint`, since takes, cancels and settles pay out of whatever account was funded
+pub(crate) fn check_escrow_vault(
+    vault: &AccountInfo,
+    escrow_account: &AccountInfo,
+    mint: &Pubkey,
+) -> ProgramResult {
+    let vault = unsafe { token_account_unchecked(vault) }
+        .map_err(|_| EscrowErrorCode::InvalidEscrowVault)?;
+    if vault.owner() != escrow_account.key() || vault.mint() != mint {
+        return Err(EscrowErrorCode::InvalidEscrowVault.into());
+    }
+    Ok(())
+}
+
+/// Accounts shared by the instructions that open an escrow
+pub(crate) struct NewEscrowAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub maker: &'a Pubkey,
+    pub escrow: &'a AccountInfo,
+    pub escrow_token_a_ata: &'a AccountInfo,
+    pub token_a_mint: &'a AccountInfo,
+    pub token_b_mint: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    // Searched for the clock sysvar
+    pub remaining: &'a [AccountInfo],
+}
+
+/// Create and initialize the maker's escrow PDA described by `ix_data`, paid for by the payer,
+/// recording the escrow token A account as its vault without funding it. Auctions are timed from
+/// the clock sysvar among the remaining accounts when one is passed.
+pub(crate) fn create_escrow_account(
+    accounts: &NewEscrowAccounts,
+    ix_data: &MakeEscrowIx,
+) -> ProgramResult {
+    use pinocchio::sysvars::Sysvar;
+
+    let NewEscrowAccounts {
+        payer: payer_account,
+        maker,
+        escrow: escrow_account,
+        escrow_token_a_ata,
+        token_a_mint,
+        token_b_mint,
+        config: config_account,
+        remaining: remaining_accounts,
+    } = *accounts;
+
+    if !escrow_account.data_is_empty() {
+        return Err(EscrowErrorCode::EscrowAlreadyExists.into());
+    }
+
+    // Token A may be a classic or Token-2022 mint; token B a classic mint or native SOL
+    let priced_in_sol = token_b_mint.key() == &SOL_TOKEN_B_MINT;
+    if !is_token_program(unsafe { token_a_mint.owner() })
+        || (!priced_in_sol && unsafe { token_b_mint.owner() } != &ID)
+    {
+        return Err(EscrowErrorCode::InvalidTokenOwner.into());
+    }
+    // Trading a mint for itself is a self-swap
+    if token_a_mint.key() == token_b_mint.key() {
+        return Err(EscrowErrorCode::IdenticalMints.into());
+    }
+    // Lamports are paid straight to the maker's wallet
+    if priced_in_sol && ix_data.token_b_vault != [0; 32] {
+        return Err(EscrowErrorCode::InvalidProceedsVault.into());
+    }
+
+    if let Some(config) = Config::load(config_account)? {
+        if !config.is_token_b_mint_allowed(token_b_mint.key()) {
+            return Err(EscrowErrorCode::MintNotAllowed.into());
+        }
+        if matches!(
+            ix_data.escrow_type,
+            EscrowType::DutchAuction | EscrowType::English
+        ) && config.max_auction_duration != 0
+            && ix_data.duration > config.max_auction_duration
+        {
+            return Err(EscrowErrorCode::InvalidDuration.into());
+        }
+    }
+
+    if ix_data.volume_discount_bps > 10_000 {
+        return Err(EscrowErrorCode::InvalidDiscount.into());
+    }
+
+    // Protocol fees are skimmed from token B transfers on Simple and Partial takes only
+    if ix_data.fee_bps != 0 {
+        if ix_data.fee_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if !matches!(
+            ix_data.escrow_type,
+            EscrowType::Simple | EscrowType::Partial
+        ) || priced_in_sol
+        {
+            return Err(EscrowErrorCode::InvalidEscrowType.into());
+        }
+    }
+
+    // Pinning the feed keeps takers from pricing against another asset's feed of the same oracle
+    if ix_data.escrow_type == EscrowType::Oracle && ix_data.oracle_feed == [0; 32] {
+        return Err(EscrowErrorCode::InvalidOracleFeed.into());
+    }
+
+    // Only Partial escrows can be filled in parts to begin with
+    if ix_data.all_or_nothing && ix_data.escrow_type != EscrowType::Partial {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    // A proceeds floor needs a deadline, or the maker's token A could be locked forever
+    if ix_data.proceeds_floor != 0 {
+        if ix_data.escrow_type != EscrowType::Partial {
+            return Err(EscrowErrorCode::InvalidEscrowType.into());
+        }
+        if ix_data.proceeds_deadline == 0 {
+            return Err(EscrowErrorCode::InvalidDuration.into());
+        }
+    }
+
+    // An empty escrow only locks up rent and a free one gives token A away. This covers the
+    // Dutch start price; Oracle escrows are priced by their feed instead.
+    if ix_data.token_a_amount == 0
+        || (ix_data.token_b_amount == 0 && ix_data.escrow_type != EscrowType::Oracle)
+    {
+        return Err(EscrowErrorCode::ZeroAmount.into());
+    }
+
+    // An auction can't decay upwards to a floor above its start price
+    if ix_data.escrow_type == EscrowType::DutchAuction && ix_data.end_price > ix_data.token_b_amount
+    {
+        return Err(EscrowErrorCode::InvalidAuctionParams.into());
+    }
+
+    // English auctions hold bids in an escrow-owned token B account and need a bidding window
+    if ix_data.escrow_type == EscrowType::English {
+        if priced_in_sol {
+            return Err(EscrowErrorCode::InvalidEscrowType.into());
+        }
+        if ix_data.duration == 0 {
+            return Err(EscrowErrorCode::InvalidAuctionParams.into());
+        }
+    }
+
+    // Set start_time and end_time for auctions; a wrapped end_time would end it at once
+    let (start_time, end_time) = if matches!(
+        ix_data.escrow_type,
+        EscrowType::DutchAuction | EscrowType::English
+    ) {
+        if ix_data.duration > MAX_AUCTION_DURATION {
+            return Err(EscrowErrorCode::InvalidAuctionParams.into());
+        }
+        let now = current_time(remaining_accounts)?;
+        let end_time = now
+            .checked_add(ix_data.duration)
+            .ok_or(EscrowErrorCode::InvalidAuctionParams)?;
+        (now, end_time)
+    } else {
+        (0, 0)
+    };
+
+    let ix_data = &ix_data.with_total_pricing()?;
+
+    let prefix = Escrow::namespace_prefix(ix_data.namespace)?;
+    Escrow::verify_escrow_address(
         escrow_account.key(),
-        maker_account.key(),
+        prefix,
+        maker,
         &ix_data.bump,
         &ix_data.seed,
     )?;
+    if !Escrow::is_canonical_bump(prefix, maker, &ix_data.seed, ix_data.bump) {
+        return Err(EscrowErrorCode::PdaMismatch.into());
+    }
 
     let bump_array = [ix_data.bump];
     let seed = [
-        Seed::from(Escrow::PREFIX.as_bytes()),
-        Seed::from(maker_account.key()),
+        Seed::from(prefix),
+        Seed::from(maker),
         Seed::from(&ix_data.seed),
         Seed::from(&bump_array),
     ];
@@ -68,40 +293,51 @@ pub fn make_escrow(
 
     // Create the PDA account
     CreateAccount {
-        from: maker_account,
+        from: payer_account,
         to: escrow_account,
-        lamports: Rent::get()?.minimum_balance(Escrow::LEN),
+        lamports: Rent::get()
+            .map_err(sysvar_unavailable)?
+            .minimum_balance(Escrow::LEN),
         space: Escrow::LEN as u64,
         owner: &crate::ID,
     }
     .invoke_signed(&[signer])?;
 
-    // Set start_time and end_time for Dutch auction
-    let (start_time, end_time) = if ix_data.escrow_type == EscrowType::DutchAuction {
-        let now = Clock::get()?.unix_timestamp as u64;
-        (now, now + ix_data.duration)
+    let token_a_decimals = unsafe { mint_unchecked(token_a_mint) }?.decimals();
+    let token_b_decimals = if priced_in_sol {
+        SOL_DECIMALS
     } else {
-        (0, 0)
+        unsafe { mint_unchecked(token_b_mint) }?.decimals()
     };
 
     Escrow::initialize(
         escrow_account,
-        &ix_data,
-        ix_data.seed,
-        *token_a_mint.key(),
-        *token_b_mint.key(),
-        *maker_account.key(),
-        start_time,
-        end_time,
+        ix_data,
+        &EscrowInit {
+            maker_pubkey: *maker,
+            token_a_mint: *token_a_mint.key(),
+            token_b_mint: *token_b_mint.key(),
+            start_time,
+            end_time,
+            token_a_decimals,
+            token_b_decimals,
+            vault: *escrow_token_a_ata.key(),
+            token_program: *unsafe { token_a_mint.owner() },
+        },
     )?;
 
-    TokenTransfer {
-        from: maker_token_a_ata,
-        to: escrow_token_a_ata,
-        authority: maker_account,
-        amount: ix_data.token_a_amount,
+    msg!("ESCROW_MADE id={}", Escrow::escrow_id(maker, &ix_data.seed));
+    EscrowEvent {
+        kind: EscrowEventKind::Made,
+        escrow_type: ix_data.escrow_type,
+        maker: *maker,
+        seed: ix_data.seed,
+        token_a_mint: *token_a_mint.key(),
+        token_b_mint: *token_b_mint.key(),
+        token_a_amount: ix_data.token_a_amount,
+        token_b_amount: ix_data.token_b_amount,
     }
-    .invoke()?;
+    .emit();
 
     Ok(())
 }
@@ -112,22 +348,95 @@ pub struct MakeEscrowIx {
     pub escrow_type: EscrowType,
     pub token_a_amount: u64,
     pub token_b_amount: u64,
-    pub seed: [u8; 2],
+    pub seed: [u8; ESCROW_SEED_LEN],
     pub bump: u8,
     // Dutch auction specific fields
     pub end_price: u64, // Minimum amount of token B required
     pub duration: u64,  // Auction duration in seconds (user input)
+    // Partial escrow specific fields
+    pub fill_cooldown: u64, // Minimum seconds between consecutive partial fills
+    // Token B delivery
+    pub token_b_vault: [u8; 32], // Token account receiving proceeds (zero = maker's ATA)
+    // Pricing
+    pub price_mode: u8, // PRICE_MODE_TOTAL, PRICE_MODE_PER_UNIT or PRICE_MODE_DECIMALS_NORMALIZED
+    // Partial escrow volume discount
+    pub discount_threshold: u64, // Partial takes above this token A amount get the discount (0 = disabled)
+    pub volume_discount_bps: u16, // Discount on token B charged, in basis points
+    // Token A refunds
+    pub refund_account: [u8; 32], // Token account receiving refunds (zero = maker's token A account)
+    // Partial escrow proceeds floor
+    pub proceeds_floor: u64, // Minimum total token B before token A can be returned (0 = disabled)
+    pub proceeds_deadline: u64, // Timestamp after which the floor no longer applies
+    // Oracle escrow specific fields
+    pub oracle_program: [u8; 32], // Program that must own the price feed account
+    pub oracle_max_age: u64,      // Oldest oracle price accepted on take, in seconds
+    // Offer lifetime
+    pub expiry: u64, // Timestamp after which takes are rejected (0 = no expiry)
+    // Protocol fee
+    pub fee_bps: u16, // Share of each Simple or Partial take's token B paid to the fee recipient
+    // Private escrows
+    pub allowed_taker: [u8; 32], // Only wallet allowed to take (zero = anyone)
+    // Dutch auction floor
+    pub expire_at_floor: bool, // Reject takes once the auction reaches end_time instead of selling at end_price
+    // PDA namespace
+    pub namespace: u8, // Escrow::NAMESPACE_ESCROW or Escrow::NAMESPACE_AUCTION
+    // Protocol fee recipient
+    pub fee_recipient: [u8; 32], // Wallet whose token B account collects the fee (zero = no fee)
+    // Partial escrow fill mode
+    pub all_or_nothing: bool, // Reject partial takes; the lot must be filled in one take
+    // Oracle escrow price feed
+    pub oracle_feed: [u8; 32], // Feed account takes must read; owned by oracle_program
 }
 
 impl MakeEscrowIx {
-    pub const LEN: usize = 1 + 8 + 8 + 2 + 1 + 8 + 8 + 8; // Added 24 bytes for Dutch auction fields
+    pub const LEN: usize = 1 // escrow type
+        + 1 // version
+        + 8 // token A amount
+        + 8 // token B amount
+        + ESCROW_SEED_LEN
+        + 1 // bump
+        + 8 // end price
+        + 8 // duration
+        + 8 // fill cooldown
+        + 32 // token B vault
+        + 1 // price mode
+        + 8 // discount threshold
+        + 2 // volume discount bps
+        + 32 // refund account
+        + 8 // proceeds floor
+        + 8 // proceeds deadline
+        + 32 // oracle program
+        + 8 // oracle max age
+        + 8 // expiry
+        + 2 // fee bps
+        + 32 // allowed taker
+        + 1 // expire at floor
+        + 1 // namespace
+        + 32 // fee recipient
+        + 1 // all or nothing
+        + 32; // oracle feed
+
+    /// Original layout: escrow type, version, amounts, seed and bump only
+    pub const VERSION_1: u8 = 1;
+    /// Current layout, which `pack` always writes
+    pub const VERSION_2: u8 = 2;
+    /// Length of a v1 payload; its remaining fields take their `new` defaults
+    pub const V1_LEN: usize = 1 + 1 + 8 + 8 + ESCROW_SEED_LEN + 1;
+
+    /// Token B prices are totals for the whole escrowed token A amount
+    pub const PRICE_MODE_TOTAL: u8 = 0;
+    /// Token B prices are per unit of token A
+    pub const PRICE_MODE_PER_UNIT: u8 = 1;
+    /// Partial escrows only: token B totals are quoted at token A's decimals and
+    /// scaled to token B base units on each take
+    pub const PRICE_MODE_DECIMALS_NORMALIZED: u8 = 2;
 
     pub fn new(
         escrow_type: EscrowType,
         token_a_amount: u64,
         token_b_amount: u64,
         bump: u8,
-        seed: [u8; 2],
+        seed: [u8; ESCROW_SEED_LEN],
     ) -> Self {
         Self {
             escrow_type,
@@ -137,6 +446,24 @@ impl MakeEscrowIx {
             bump,
             end_price: 0,
             duration: 0,
+            fill_cooldown: 0,
+            token_b_vault: [0; 32],
+            price_mode: Self::PRICE_MODE_TOTAL,
+            discount_threshold: 0,
+            volume_discount_bps: 0,
+            refund_account: [0; 32],
+            proceeds_floor: 0,
+            proceeds_deadline: 0,
+            oracle_program: [0; 32],
+            oracle_max_age: 0,
+            expiry: 0,
+            fee_bps: 0,
+            allowed_taker: [0; 32],
+            expire_at_floor: false,
+            namespace: Escrow::NAMESPACE_ESCROW,
+            fee_recipient: [0; 32],
+            all_or_nothing: false,
+            oracle_feed: [0; 32],
         }
     }
 
@@ -147,7 +474,7 @@ impl MakeEscrowIx {
         start_time: u64,
         end_time: u64,
         bump: u8,
-        seed: [u8; 2],
+        seed: [u8; ESCROW_SEED_LEN],
     ) -> Self {
         Self {
             escrow_type: EscrowType::DutchAuction,
@@ -157,55 +484,174 @@ impl MakeEscrowIx {
             bump,
             end_price,
             duration: end_time - start_time,
+            fill_cooldown: 0,
+            token_b_vault: [0; 32],
+            price_mode: Self::PRICE_MODE_TOTAL,
+            discount_threshold: 0,
+            volume_discount_bps: 0,
+            refund_account: [0; 32],
+            proceeds_floor: 0,
+            proceeds_deadline: 0,
+            oracle_program: [0; 32],
+            oracle_max_age: 0,
+            expiry: 0,
+            fee_bps: 0,
+            allowed_taker: [0; 32],
+            expire_at_floor: false,
+            namespace: Escrow::NAMESPACE_ESCROW,
+            fee_recipient: [0; 32],
+            all_or_nothing: false,
+            oracle_feed: [0; 32],
         }
     }
 
     pub fn pack(&self) -> [u8; Self::LEN] {
         let mut data = [0u8; Self::LEN];
         data[0] = self.escrow_type as u8;
-        data[1..9].copy_from_slice(&self.token_a_amount.to_le_bytes());
-        data[9..17].copy_from_slice(&self.token_b_amount.to_le_bytes());
-        data[17..19].copy_from_slice(&self.seed);
-        data[19] = self.bump;
+        data[1] = Self::VERSION_2;
+        data[2..10].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[10..18].copy_from_slice(&self.token_b_amount.to_le_bytes());
+        data[18..26].copy_from_slice(&self.seed);
+        data[26] = self.bump;
 
         // Pack Dutch auction fields
         let end_price_bytes = self.end_price.to_le_bytes();
-        data[20..28].copy_from_slice(&end_price_bytes);
+        data[27..35].copy_from_slice(&end_price_bytes);
         let duration_bytes = self.duration.to_le_bytes();
-        data[28..36].copy_from_slice(&duration_bytes);
+        data[35..43].copy_from_slice(&duration_bytes);
+
+        // Pack partial escrow fields
+        data[43..51].copy_from_slice(&self.fill_cooldown.to_le_bytes());
+
+        // Pack token B delivery fields
+        data[51..83].copy_from_slice(&self.token_b_vault);
+
+        // Pack pricing fields
+        data[83] = self.price_mode;
+
+        // Pack volume discount fields
+        data[84..92].copy_from_slice(&self.discount_threshold.to_le_bytes());
+        data[92..94].copy_from_slice(&self.volume_discount_bps.to_le_bytes());
+
+        // Pack refund fields
+        data[94..126].copy_from_slice(&self.refund_account);
+
+        // Pack proceeds floor fields
+        data[126..134].copy_from_slice(&self.proceeds_floor.to_le_bytes());
+        data[134..142].copy_from_slice(&self.proceeds_deadline.to_le_bytes());
+
+        // Pack oracle fields
+        data[142..174].copy_from_slice(&self.oracle_program);
+        data[174..182].copy_from_slice(&self.oracle_max_age.to_le_bytes());
+
+        // Pack expiry
+        data[182..190].copy_from_slice(&self.expiry.to_le_bytes());
+
+        // Pack protocol fee
+        data[190..192].copy_from_slice(&self.fee_bps.to_le_bytes());
+
+        // Pack allowed taker
+        data[192..224].copy_from_slice(&self.allowed_taker);
+
+        // Pack Dutch auction floor flag
+        data[224] = self.expire_at_floor as u8;
+
+        // Pack PDA namespace
+        data[225] = self.namespace;
+
+        // Pack protocol fee recipient
+        data[226..258].copy_from_slice(&self.fee_recipient);
+
+        // Pack all-or-nothing flag
+        data[258] = self.all_or_nothing as u8;
+
+        // Pack oracle feed
+        data[259..291].copy_from_slice(&self.oracle_feed);
 
         data
     }
 
     pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        // Check up front so a truncated payload can't reach the fixed-offset reads below
+        let len = match data.get(1) {
+            Some(&Self::VERSION_1) => Self::V1_LEN,
+            Some(&Self::VERSION_2) => Self::LEN,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        if data.len() < len {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let [escrow_type] = Self::read(data, 0)?;
         let escrow_type =
-            EscrowType::try_from(data[0]).map_err(|_| ProgramError::InvalidInstructionData)?;
-        let token_a_amount = u64::from_le_bytes(
-            data[1..9]
-                .try_into()
-                .map_err(|_| ProgramError::InvalidInstructionData)?,
-        );
-        let token_b_amount = u64::from_le_bytes(
-            data[9..17]
-                .try_into()
-                .map_err(|_| ProgramError::InvalidInstructionData)?,
-        );
-        let seed = data[17..19]
-            .try_into()
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
-        let bump = data[19];
+            EscrowType::try_from(escrow_type).map_err(|_| ProgramError::InvalidInstructionData)?;
+        let token_a_amount = u64::from_le_bytes(Self::read(data, 2)?);
+        let token_b_amount = u64::from_le_bytes(Self::read(data, 10)?);
+        let seed = Self::read(data, 18)?;
+        let [bump] = Self::read(data, 26)?;
+
+        if data[1] == Self::VERSION_1 {
+            return Ok(Self::new(
+                escrow_type,
+                token_a_amount,
+                token_b_amount,
+                bump,
+                seed,
+            ));
+        }
 
         // Unpack Dutch auction fields
-        let end_price = u64::from_le_bytes(
-            data[20..28]
-                .try_into()
-                .map_err(|_| ProgramError::InvalidInstructionData)?,
-        );
-        let duration = u64::from_le_bytes(
-            data[28..36]
-                .try_into()
-                .map_err(|_| ProgramError::InvalidInstructionData)?,
-        );
+        let end_price = u64::from_le_bytes(Self::read(data, 27)?);
+        let duration = u64::from_le_bytes(Self::read(data, 35)?);
+
+        // Unpack partial escrow fields
+        let fill_cooldown = u64::from_le_bytes(Self::read(data, 43)?);
+
+        // Unpack token B delivery fields
+        let token_b_vault = Self::read(data, 51)?;
+
+        // Unpack pricing fields
+        let [price_mode] = Self::read(data, 83)?;
+
+        // Unpack volume discount fields
+        let discount_threshold = u64::from_le_bytes(Self::read(data, 84)?);
+        let volume_discount_bps = u16::from_le_bytes(Self::read(data, 92)?);
+
+        // Unpack refund fields
+        let refund_account = Self::read(data, 94)?;
+
+        // Unpack proceeds floor fields
+        let proceeds_floor = u64::from_le_bytes(Self::read(data, 126)?);
+        let proceeds_deadline = u64::from_le_bytes(Self::read(data, 134)?);
+
+        // Unpack oracle fields
+        let oracle_program = Self::read(data, 142)?;
+        let oracle_max_age = u64::from_le_bytes(Self::read(data, 174)?);
+
+        // Unpack expiry
+        let expiry = u64::from_le_bytes(Self::read(data, 182)?);
+
+        // Unpack protocol fee
+        let fee_bps = u16::from_le_bytes(Self::read(data, 190)?);
+
+        // Unpack allowed taker
+        let allowed_taker = Self::read(data, 192)?;
+
+        // Unpack Dutch auction floor flag
+        let expire_at_floor = Self::read_flag(data, 224)?;
+
+        // Unpack PDA namespace
+        let [namespace] = Self::read(data, 225)?;
+        Escrow::namespace_prefix(namespace)?;
+
+        // Unpack protocol fee recipient
+        let fee_recipient = Self::read(data, 226)?;
+
+        // Unpack all-or-nothing flag
+        let all_or_nothing = Self::read_flag(data, 258)?;
+
+        // Unpack oracle feed
+        let oracle_feed = Self::read(data, 259)?;
 
         Ok(Self {
             escrow_type,
@@ -215,6 +661,238 @@ impl MakeEscrowIx {
             bump,
             end_price,
             duration,
+            fill_cooldown,
+            token_b_vault,
+            price_mode,
+            discount_threshold,
+            volume_discount_bps,
+            refund_account,
+            proceeds_floor,
+            proceeds_deadline,
+            oracle_program,
+            oracle_max_age,
+            expiry,
+            fee_bps,
+            allowed_taker,
+            expire_at_floor,
+            namespace,
+            fee_recipient,
+            all_or_nothing,
+            oracle_feed,
+        })
+    }
+
+    /// The `N` bytes at `offset`
+    fn read<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+        data.get(offset..offset + N)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    /// The boolean flag at `offset`, which must be 0 or 1
+    fn read_flag(data: &[u8], offset: usize) -> Result<bool, ProgramError> {
+        match Self::read(data, offset)? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    /// Resolve per-unit prices into totals for the escrowed token A amount
+    pub fn with_total_pricing(&self) -> Result<Self, ProgramError> {
+        let per_unit_total = |price: u64| {
+            u64::try_from(price as u128 * self.token_a_amount as u128)
+                .map_err(|_| ProgramError::ArithmeticOverflow)
+        };
+
+        match self.price_mode {
+            Self::PRICE_MODE_TOTAL => Ok(*self),
+            Self::PRICE_MODE_PER_UNIT => Ok(Self {
+                token_b_amount: per_unit_total(self.token_b_amount)?,
+                end_price: per_unit_total(self.end_price)?,
+                price_mode: Self::PRICE_MODE_TOTAL,
+                ..*self
+            }),
+            Self::PRICE_MODE_DECIMALS_NORMALIZED if self.escrow_type == EscrowType::Partial => {
+                Ok(*self)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Chained construction of a `MakeEscrowIx` for clients and tests. `build` checks the
+/// pricing invariants up front so a malformed make is caught before it is sent; every other
+/// field keeps the `MakeEscrowIx::new` default and can be set on the built instruction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MakeEscrowIxBuilder {
+    escrow_type: Option<EscrowType>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    end_price: u64,
+    duration: u64,
+    seed: [u8; ESCROW_SEED_LEN],
+    bump: u8,
+}
+
+impl MakeEscrowIxBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offer all of `token_a_amount` for `token_b_amount`
+    pub fn simple(self, token_a_amount: u64, token_b_amount: u64) -> Self {
+        self.escrow(EscrowType::Simple, token_a_amount, token_b_amount)
+    }
+
+    /// Offer `token_a_amount` to be filled in parts, pro rata to `token_b_amount`
+    pub fn partial(self, token_a_amount: u64, token_b_amount: u64) -> Self {
+        self.escrow(EscrowType::Partial, token_a_amount, token_b_amount)
+    }
+
+    /// Auction `token_a_amount` at a price decaying from `start_price` to `end_price`
+    /// over `duration` seconds
+    pub fn dutch(
+        self,
+        token_a_amount: u64,
+        start_price: u64,
+        end_price: u64,
+        duration: u64,
+    ) -> Self {
+        Self {
+            end_price,
+            duration,
+            ..self.escrow(EscrowType::DutchAuction, token_a_amount, start_price)
+        }
+    }
+
+    pub fn seed(self, seed: [u8; ESCROW_SEED_LEN]) -> Self {
+        Self { seed, ..self }
+    }
+
+    pub fn bump(self, bump: u8) -> Self {
+        Self { bump, ..self }
+    }
+
+    fn escrow(self, escrow_type: EscrowType, token_a_amount: u64, token_b_amount: u64) -> Self {
+        Self {
+            escrow_type: Some(escrow_type),
+            token_a_amount,
+            token_b_amount,
+            ..self
+        }
+    }
+
+    pub fn build(&self) -> Result<MakeEscrowIx, ProgramError> {
+        let escrow_type = self
+            .escrow_type
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        if self.token_a_amount == 0 || self.token_b_amount == 0 {
+            return Err(EscrowErrorCode::ZeroAmount.into());
+        }
+        if escrow_type == EscrowType::DutchAuction
+            && (self.end_price > self.token_b_amount || self.duration == 0)
+        {
+            return Err(EscrowErrorCode::InvalidAuctionParams.into());
+        }
+
+        Ok(MakeEscrowIx {
+            end_price: self.end_price,
+            duration: self.duration,
+            ..MakeEscrowIx::new(
+                escrow_type,
+                self.token_a_amount,
+                self.token_b_amount,
+                self.bump,
+                self.seed,
+            )
         })
     }
+
+    /// The built instruction's data, without the make discriminator
+    pub fn pack(&self) -> Result<[u8; MakeEscrowIx::LEN], ProgramError> {
+        self.build().map(|ix| ix.pack())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_makes_simple_escrows() {
+        let ix = MakeEscrowIxBuilder::new()
+            .simple(1000, 2000)
+            .seed(7u64.to_le_bytes())
+            .bump(254)
+            .build();
+        assert_eq!(
+            ix,
+            Ok(MakeEscrowIx::new(
+                EscrowType::Simple,
+                1000,
+                2000,
+                254,
+                7u64.to_le_bytes()
+            ))
+        );
+    }
+
+    #[test]
+    fn builder_makes_partial_escrows() {
+        let data = MakeEscrowIxBuilder::new()
+            .partial(1000, 2000)
+            .pack()
+            .unwrap();
+        let ix = MakeEscrowIx::unpack(&data).unwrap();
+        assert_eq!(ix.escrow_type, EscrowType::Partial);
+        assert_eq!((ix.token_a_amount, ix.token_b_amount), (1000, 2000));
+    }
+
+    #[test]
+    fn builder_makes_dutch_auctions() {
+        let ix = MakeEscrowIxBuilder::new()
+            .dutch(1000, 10000, 5000, 3600)
+            .bump(255)
+            .build();
+        assert_eq!(
+            ix,
+            Ok(MakeEscrowIx::new_dutch_auction(
+                1000,
+                10000,
+                5000,
+                0,
+                3600,
+                255,
+                [0; ESCROW_SEED_LEN]
+            ))
+        );
+    }
+
+    #[test]
+    fn builder_rejects_incomplete_escrows() {
+        assert_eq!(
+            MakeEscrowIxBuilder::new()
+                .seed([1; ESCROW_SEED_LEN])
+                .build(),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            MakeEscrowIxBuilder::new()
+                .dutch(1000, 10000, 5000, 0)
+                .build(),
+            Err(EscrowErrorCode::InvalidAuctionParams.into())
+        );
+        assert_eq!(
+            MakeEscrowIxBuilder::new().dutch(1000, 0, 0, 3600).build(),
+            Err(EscrowErrorCode::ZeroAmount.into())
+        );
+        assert_eq!(
+            MakeEscrowIxBuilder::new()
+                .dutch(1000, 5000, 10000, 3600)
+                .build(),
+            Err(EscrowErrorCode::InvalidAuctionParams.into())
+        );
+    }
 }
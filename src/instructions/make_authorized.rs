@@ -0,0 +1,143 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+    ProgramResult,
+};
+use pinocchio_pubkey::pubkey;
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::{
+        check_escrow_vault, check_token_program, create_escrow_account, MakeEscrowIx,
+        NewEscrowAccounts,
+    },
+    token::{token_account_unchecked, Transfer as TokenTransfer},
+};
+
+/// Native ed25519 signature verification program
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Message a maker signs off-chain to authorize an escrow: escrow PDA || packed make params
+pub const AUTHORIZATION_MESSAGE_LEN: usize = 32 + MakeEscrowIx::LEN;
+
+/// Create an escrow on behalf of a maker who authorized it off-chain.
+/// The instruction immediately before this one must be an ed25519 verification of
+/// the maker's signature over the authorization message; the payer funds token A.
+pub fn make_escrow_authorized(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_account, payer_token_a_ata, escrow_account, escrow_token_a_ata, token_a_mint, token_b_mint, _system_program, maker_account, instructions_sysvar, config_account, remaining_accounts @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_account.is_signer() {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    if payer_token_a_ata.data_is_empty() {
+        return Err(EscrowErrorCode::InvalidMakerTokenAccount.into());
+    }
+
+    let payer_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(payer_token_a_ata) }?;
+    if payer_token_a_account.owner() != payer_account.key() {
+        return Err(EscrowErrorCode::InvalidTokenOwner.into());
+    }
+
+    check_token_program(token_a_mint, &[payer_token_a_ata, escrow_token_a_ata])?;
+    check_escrow_vault(escrow_token_a_ata, escrow_account, token_a_mint.key())?;
+
+    let ix_data = MakeEscrowIx::unpack(instruction_data)?;
+
+    let message = authorization_message(escrow_account.key(), &ix_data);
+    let instructions = Instructions::try_from(instructions_sysvar)?;
+    verify_ed25519_authorization(
+        &instructions.get_instruction_relative(-1)?,
+        maker_account.key(),
+        &message,
+    )?;
+
+    create_escrow_account(
+        &NewEscrowAccounts {
+            payer: payer_account,
+            maker: maker_account.key(),
+            escrow: escrow_account,
+            escrow_token_a_ata,
+            token_a_mint,
+            token_b_mint,
+            config: config_account,
+            remaining: remaining_accounts,
+        },
+        &ix_data,
+    )?;
+
+    TokenTransfer {
+        token_program: unsafe { token_a_mint.owner() },
+        from: payer_token_a_ata,
+        to: escrow_token_a_ata,
+        authority: payer_account,
+        amount: ix_data.token_a_amount,
+    }
+    .invoke()?;
+
+    Ok(())
+}
+
+/// Build the message a maker signs to authorize `ix_data` for the escrow at `escrow`
+pub fn authorization_message(
+    escrow: &Pubkey,
+    ix_data: &MakeEscrowIx,
+) -> [u8; AUTHORIZATION_MESSAGE_LEN] {
+    let mut message = [0u8; AUTHORIZATION_MESSAGE_LEN];
+    message[..32].copy_from_slice(escrow);
+    message[32..].copy_from_slice(&ix_data.pack());
+    message
+}
+
+/// Check that `ed25519_ix` verified exactly one signature by `signer` over `message`,
+/// with all of its inputs carried in its own instruction data
+fn verify_ed25519_authorization(
+    ed25519_ix: &IntrospectedInstruction,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    if ed25519_ix.get_program_id() != &ED25519_PROGRAM_ID {
+        return Err(EscrowErrorCode::InvalidAuthorization.into());
+    }
+
+    // Header: signature count and padding, then one set of u16 offsets
+    let data = ed25519_ix.get_instruction_data();
+    if data.len() < 16 || data[0] != 1 {
+        return Err(EscrowErrorCode::InvalidAuthorization.into());
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    if signature_ix_index != u16::MAX
+        || public_key_ix_index != u16::MAX
+        || message_ix_index != u16::MAX
+    {
+        return Err(EscrowErrorCode::InvalidAuthorization.into());
+    }
+
+    let signed_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    if signed_key != Some(signer.as_slice()) || signed_message != Some(message) {
+        return Err(EscrowErrorCode::InvalidAuthorization.into());
+    }
+
+    Ok(())
+}
@@ -1,5 +1,47 @@
+mod cancel;
+mod config;
+mod deposit;
+mod english;
+mod fees;
+mod fill_history;
+mod fills;
+mod finalize;
 mod make;
+mod make_authorized;
+mod quote;
+mod replace;
+mod reprice;
+mod status;
+mod sweep;
 mod take;
+mod take_batch;
+mod take_best;
+mod take_limit;
+mod take_sol;
+mod time_remaining;
+mod update;
+mod withdraw;
 
+pub use cancel::*;
+pub use config::*;
+pub use deposit::*;
+pub use english::*;
+pub use fees::*;
+pub use fill_history::*;
+pub use fills::*;
+pub use finalize::*;
 pub use make::*;
+pub use make_authorized::*;
+pub use quote::*;
+pub use replace::*;
+pub use reprice::*;
+pub use status::*;
+pub use sweep::*;
 pub use take::*;
+pub use take_batch::*;
+pub use take_best::*;
+pub use take_limit::*;
+pub use take_sol::*;
+pub use time_remaining::*;
+pub use update::*;
+pub use withdraw::*;
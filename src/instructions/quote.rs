@@ -0,0 +1,53 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    states::{try_from_account_info, Escrow, EscrowType},
+};
+
+/// Quote the token B a take of the whole remaining lot requires right now, priced like the take
+/// itself (Dutch decay over partial fills, decimals normalization, volume discount), so clients
+/// can simulate it instead of re-implementing the pricing. Logs `ESCROW_QUOTE id=...
+/// token_b_amount=...` and writes the amount to return data as a little-endian u64.
+pub fn quote(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+    // Oracle escrows are priced by their feed and English auctions by their bids
+    if matches!(escrow.escrow_type, EscrowType::Oracle | EscrowType::English) {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    let token_b_amount = escrow
+        .quote_fill(escrow.token_a_amount, current_time)?
+        .ok_or(if escrow.is_expired(current_time) {
+            EscrowErrorCode::EscrowExpired
+        } else {
+            EscrowErrorCode::EscrowNotActive
+        })?;
+
+    msg!(
+        "ESCROW_QUOTE id={} token_b_amount={}",
+        Escrow::escrow_id(&escrow.maker_pubkey, &escrow.seed),
+        token_b_amount
+    );
+    set_return_data(&token_b_amount.to_le_bytes());
+
+    Ok(())
+}
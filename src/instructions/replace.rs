@@ -0,0 +1,145 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    close::{close_escrow_account, close_token_account},
+    error::EscrowErrorCode,
+    instructions::{
+        check_escrow_vault, check_proceeds_floor, check_token_program, create_escrow_account,
+        load_maker_escrow, MakeEscrowIx, NewEscrowAccounts,
+    },
+    states::Escrow,
+    token::{token_account_unchecked, Transfer as TokenTransfer},
+};
+
+/// Cancel an escrow and create its replacement in one step.
+/// Token A moves directly from the old escrow's token account into the new one;
+/// anything not carried over is refunded to the maker, or to the old escrow's refund account
+/// if it has one.
+pub fn replace_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, old_escrow_account, old_escrow_token_a_ata, new_escrow_account, new_escrow_token_a_ata, token_a_mint, token_b_mint, _system_program, _token_program, config_account, remaining_accounts @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let old_escrow = load_maker_escrow(maker_account, old_escrow_account)?;
+    check_proceeds_floor(old_escrow)?;
+    if old_escrow.has_bids() {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    }
+    old_escrow.transition(Escrow::STATE_CANCELLED)?;
+
+    if old_escrow_account.key() == new_escrow_account.key() {
+        return Err(EscrowErrorCode::DuplicateAccount.into());
+    }
+
+    if token_a_mint.key() != &old_escrow.token_a_mint {
+        return Err(EscrowErrorCode::MintMismatch.into());
+    }
+
+    check_token_program(
+        token_a_mint,
+        &[
+            maker_token_a_ata,
+            old_escrow_token_a_ata,
+            new_escrow_token_a_ata,
+        ],
+    )?;
+    check_escrow_vault(
+        new_escrow_token_a_ata,
+        new_escrow_account,
+        token_a_mint.key(),
+    )?;
+
+    // Carry over token A only from the vault the old escrow was funded through
+    if old_escrow_token_a_ata.key() != &old_escrow.vault {
+        return Err(EscrowErrorCode::InvalidEscrowVault.into());
+    }
+
+    let old_escrow_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(old_escrow_token_a_ata) }?;
+    if old_escrow_token_a_account.mint() != &old_escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    let available = old_escrow_token_a_account.amount();
+
+    // Token A not carried over goes to the refund account the maker designated at make, if any
+    if old_escrow.refund_account != [0; 32] && maker_token_a_ata.key() != &old_escrow.refund_account
+    {
+        return Err(EscrowErrorCode::InvalidRefundAccount.into());
+    }
+    let refund_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(maker_token_a_ata) }?;
+    if refund_token_a_account.mint() != &old_escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    let ix_data = MakeEscrowIx::unpack(instruction_data)?;
+    if ix_data.token_a_amount > available {
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    create_escrow_account(
+        &NewEscrowAccounts {
+            payer: maker_account,
+            maker: maker_account.key(),
+            escrow: new_escrow_account,
+            escrow_token_a_ata: new_escrow_token_a_ata,
+            token_a_mint,
+            token_b_mint,
+            config: config_account,
+            remaining: remaining_accounts,
+        },
+        &ix_data,
+    )?;
+
+    let token_program = old_escrow.token_program;
+    let bump_array = [old_escrow.bump];
+    let seed = [
+        Seed::from(old_escrow.prefix()),
+        Seed::from(maker_account.key()),
+        Seed::from(&old_escrow.seed),
+        Seed::from(&bump_array),
+    ];
+    let signer = Signer::from(&seed);
+
+    TokenTransfer {
+        token_program: &token_program,
+        from: old_escrow_token_a_ata,
+        to: new_escrow_token_a_ata,
+        authority: old_escrow_account,
+        amount: ix_data.token_a_amount,
+    }
+    .invoke_signed(core::slice::from_ref(&signer))?;
+
+    if available > ix_data.token_a_amount {
+        TokenTransfer {
+            token_program: &token_program,
+            from: old_escrow_token_a_ata,
+            to: maker_token_a_ata,
+            authority: old_escrow_account,
+            amount: available - ix_data.token_a_amount,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+    }
+
+    close_token_account(
+        &token_program,
+        old_escrow_token_a_ata,
+        maker_account,
+        old_escrow_account,
+        &signer,
+    )?;
+    close_escrow_account(old_escrow_account, maker_account)
+}
@@ -0,0 +1,54 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    instructions::load_maker_escrow,
+    states::EscrowType,
+};
+
+/// Restart a Dutch auction's decay from a new start price. The auction keeps its end time and
+/// end price, so the new price decays over whatever duration remains. Like the make price, the
+/// start price is for the original lot.
+pub fn reprice_dutch(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let start_price = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if escrow.escrow_type != EscrowType::DutchAuction {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    if start_price == 0 || start_price < escrow.end_price {
+        return Err(EscrowErrorCode::InvalidPriceRange.into());
+    }
+
+    let now = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    if now >= escrow.end_time {
+        return Err(EscrowErrorCode::InvalidDuration.into());
+    }
+
+    escrow.start_price = start_price;
+    escrow.token_b_amount = start_price;
+    escrow.start_time = now;
+    escrow.duration = escrow.end_time - now;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::sysvar_unavailable,
+    states::{try_from_account_info, DataLen, Escrow, EscrowStatus, EscrowType},
+    token::token_account_unchecked,
+};
+
+/// Maximum number of escrows read by a single batch status instruction
+pub const MAX_BATCH_STATUS_ESCROWS: usize = 16;
+
+/// Status byte reported for accounts that aren't escrows of this program
+pub const BATCH_STATUS_INVALID: u8 = 0xFF;
+
+/// Per-escrow batch status record: status byte followed by the remaining token A amount
+pub const BATCH_STATUS_RECORD_LEN: usize = 1 + 8;
+
+/// `check_escrow` violation bits: the escrow account isn't a valid escrow of this program
+pub const ESCROW_CHECK_INVALID_ESCROW: u8 = 1 << 0;
+/// The escrow token account's authority isn't the escrow PDA
+pub const ESCROW_CHECK_VAULT_AUTHORITY: u8 = 1 << 1;
+/// The escrow token account (or the mint passed) doesn't match the escrow's token A mint
+pub const ESCROW_CHECK_VAULT_MINT: u8 = 1 << 2;
+/// The escrow token account holds less than the escrow's remaining token A amount
+pub const ESCROW_CHECK_VAULT_BALANCE: u8 = 1 << 3;
+/// The escrow token account isn't a token account of the mint's token program
+pub const ESCROW_CHECK_VAULT_PROGRAM: u8 = 1 << 4;
+
+/// Byte offset of the escrow type within an escrow account
+const ESCROW_TYPE_OFFSET: usize = core::mem::offset_of!(Escrow, escrow_type);
+
+/// Writes a single status byte for the escrow to return data.
+/// An escrow account that no longer exists is reported as closed.
+pub fn escrow_status(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let status =
+        if escrow_account.data_is_empty() || unsafe { escrow_account.owner() } != &crate::ID {
+            EscrowStatus::Closed
+        } else {
+            let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+            let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+            escrow.status(current_time)
+        };
+
+    set_return_data(&[status as u8]);
+
+    Ok(())
+}
+
+/// Writes a status record for each escrow account passed, in order, to return data.
+/// Accounts that aren't escrows of this program are flagged with `BATCH_STATUS_INVALID`
+/// instead of failing the whole read.
+pub fn batch_status(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    if accounts.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if accounts.len() > MAX_BATCH_STATUS_ESCROWS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    let mut data = [0u8; MAX_BATCH_STATUS_ESCROWS * BATCH_STATUS_RECORD_LEN];
+    for (record, escrow_account) in data
+        .chunks_exact_mut(BATCH_STATUS_RECORD_LEN)
+        .zip(accounts.iter())
+    {
+        let (status, remaining) = if escrow_account.data_is_empty() {
+            (EscrowStatus::Closed as u8, 0)
+        } else if !is_escrow_account(escrow_account)? {
+            (BATCH_STATUS_INVALID, 0)
+        } else {
+            let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+            (escrow.status(current_time) as u8, escrow.token_a_amount)
+        };
+
+        record[0] = status;
+        record[1..].copy_from_slice(&remaining.to_le_bytes());
+    }
+
+    set_return_data(&data[..accounts.len() * BATCH_STATUS_RECORD_LEN]);
+
+    Ok(())
+}
+
+/// Diagnostic read for operators: checks an escrow against its token A account and mint
+/// and writes a bitmask of `ESCROW_CHECK_*` violations to return data (0 = healthy).
+pub fn check_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, escrow_token_a_ata, token_a_mint, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if escrow_account.data_is_empty() || !is_escrow_account(escrow_account)? {
+        set_return_data(&[ESCROW_CHECK_INVALID_ESCROW]);
+        return Ok(());
+    }
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+    let mut violations = 0;
+    if token_a_mint.key() != &escrow.token_a_mint {
+        violations |= ESCROW_CHECK_VAULT_MINT;
+    }
+
+    // The remaining checks need a token account of the escrow's token program
+    let vault = match unsafe { token_account_unchecked(escrow_token_a_ata) } {
+        Ok(vault)
+            if unsafe { escrow_token_a_ata.owner() == token_a_mint.owner() }
+                && unsafe { escrow_token_a_ata.owner() } == &escrow.token_program =>
+        {
+            vault
+        }
+        _ => {
+            set_return_data(&[violations | ESCROW_CHECK_VAULT_PROGRAM]);
+            return Ok(());
+        }
+    };
+    if vault.owner() != escrow_account.key() {
+        violations |= ESCROW_CHECK_VAULT_AUTHORITY;
+    }
+    if vault.mint() != &escrow.token_a_mint {
+        violations |= ESCROW_CHECK_VAULT_MINT;
+    }
+    if vault.amount() < escrow.token_a_amount {
+        violations |= ESCROW_CHECK_VAULT_BALANCE;
+    }
+
+    set_return_data(&[violations]);
+
+    Ok(())
+}
+
+/// Whether a non-empty account is an escrow of this program: owned by it, sized like an
+/// escrow and carrying a known escrow type
+fn is_escrow_account(account: &AccountInfo) -> Result<bool, ProgramError> {
+    Ok(unsafe { account.owner() } == &crate::ID
+        && account.data_len() == Escrow::LEN
+        && EscrowType::try_from(account.try_borrow_data()?[ESCROW_TYPE_OFFSET]).is_ok())
+}
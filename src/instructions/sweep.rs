@@ -0,0 +1,69 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::{check_escrow_vault, load_maker_escrow},
+    token::Transfer as TokenTransfer,
+};
+
+/// Recover token B left in a token account owned by the escrow PDA, such as dust from a
+/// mistaken direct transfer, by sending its whole balance to the maker's token B account.
+/// An English auction's token B account holds the leading bid until settlement, so it can't
+/// be swept while the auction has bids.
+pub fn sweep_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_b_ata, escrow_account, escrow_token_b_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+    if escrow.has_bids() {
+        return Err(EscrowErrorCode::EscrowStillActive.into());
+    }
+
+    check_escrow_vault(escrow_token_b_ata, escrow_account, &escrow.token_b_mint)?;
+    let escrow_token_b_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(escrow_token_b_ata) }?;
+    let amount = escrow_token_b_account.amount();
+    if amount == 0 {
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    let maker_token_b_account: &TokenAccount =
+        unsafe { TokenAccount::from_account_info_unchecked(maker_token_b_ata) }?;
+    if maker_token_b_account.mint() != &escrow.token_b_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    if maker_token_b_account.owner() != maker_account.key() {
+        return Err(EscrowErrorCode::InvalidTokenOwner.into());
+    }
+
+    let bump_array = [escrow.bump];
+    let seed = [
+        Seed::from(escrow.prefix()),
+        Seed::from(maker_account.key()),
+        Seed::from(&escrow.seed),
+        Seed::from(&bump_array),
+    ];
+
+    TokenTransfer {
+        token_program: &pinocchio_token::ID,
+        from: escrow_token_b_ata,
+        to: maker_token_b_ata,
+        authority: escrow_account,
+        amount,
+    }
+    .invoke_signed(&[Signer::from(&seed)])
+}
@@ -1,181 +1,652 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    msg,
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::{instructions::Transfer as TokenTransfer, state::TokenAccount};
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+use pinocchio_token::state::TokenAccount;
 
 use crate::{
+    clock::{current_time, CLOCK_SYSVAR_ID},
+    close::{close_escrow_account, close_token_account},
     error::EscrowErrorCode,
-    states::{try_from_account_info_mut, Escrow, EscrowType},
+    events::{EscrowEvent, EscrowEventKind},
+    states::{hashv, try_from_account_info_mut, Config, Escrow, EscrowType, OraclePrice},
+    token::{token_account_unchecked, Transfer as TokenTransfer},
 };
 
+/// Take (part of) an escrow. Take never inspects the surrounding instructions and only closes
+/// the escrow's own accounts, so it can sit anywhere inside a larger transaction.
+///
+/// The first seven accounts are positional: escrow, escrow token A account, maker, maker token B
+/// account, taker, taker token A account and taker token B account. The rest may follow in any
+/// order and are located by key or owner:
+/// - the config PDA, found by its address
+/// - for Oracle escrows, the price feed, found by its address being the escrow's `oracle_feed`
+/// - for escrows with a protocol fee, the fee collector, found as a token account of the
+///   escrow's `fee_recipient`
+/// - for split payments, the taker's second token B account, found as a token program account
+/// - optionally the clock sysvar, found by its address; time checks read it instead of
+///   `Clock::get()` when it is passed
+///
+/// Accounts that can't be told apart this way fall back to their position: the first trailing
+/// account stands in for the config, and the first one left after it for the feed or the split
+/// account, and after those for the fee collector. Programs and sysvars the take relies on only
+/// need to be present in the transaction.
 pub fn take_escrow(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
     // Escrow and maker related accounts
-    let [escrow_account, escrow_token_a_ata, maker_account, maker_token_b_ata, taker_account, taker_token_a_ata, taker_token_b_ata, _remaing @ ..] =
+    let [escrow_account, escrow_token_a_ata, maker_account, maker_token_b_ata, taker_account, taker_token_a_ata, taker_token_b_ata, trailing @ ..] =
         &accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let config_account = find_account(trailing.iter(), |account| account.key() == &Config::ADDRESS)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let remaining = || {
+        trailing.iter().filter(|account| {
+            account.key() != config_account.key() && account.key() != &CLOCK_SYSVAR_ID
+        })
+    };
+
+    // Reject lookalike accounts before reading any escrow state
+    if unsafe { escrow_account.owner() } != &crate::ID {
+        return Err(EscrowErrorCode::InvalidEscrowOwner.into());
+    }
+
     let escrow = unsafe { try_from_account_info_mut::<Escrow>(escrow_account) }?;
 
-    Escrow::validate_escrow_pda(
+    // Token B must go to the maker who created the escrow
+    if maker_account.key() != &escrow.maker_pubkey {
+        return Err(EscrowErrorCode::InvalidMaker.into());
+    }
+
+    Escrow::verify_escrow_address(
         escrow_account.key(),
+        escrow.prefix(),
         maker_account.key(),
         &escrow.bump,
         &escrow.seed,
     )?;
 
+    // Token A may only leave through the vault the make funded
+    if escrow_token_a_ata.key() != &escrow.vault {
+        return Err(EscrowErrorCode::InvalidEscrowVault.into());
+    }
+
     if !taker_account.is_signer() {
         return Err(EscrowErrorCode::InvalidMaker.into());
     }
 
+    if !escrow.allows_taker(taker_account.key()) {
+        return Err(EscrowErrorCode::TakerNotAllowed.into());
+    }
+
+    if escrow.is_self_take(taker_account.key()) {
+        return Err(EscrowErrorCode::MakerCannotTake.into());
+    }
+
+    if let Some(config) = Config::load(config_account)? {
+        if config.takes_paused != 0 {
+            return Err(EscrowErrorCode::ProgramPaused.into());
+        }
+    }
+
+    // English auctions are won by bidding and settled once bidding closes
+    if escrow.escrow_type == EscrowType::English {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    // Held as completed while the take runs, so re-entering it fails; a take that leaves
+    // some of the lot reopens the escrow
+    escrow.transition(Escrow::STATE_COMPLETED)?;
+
+    // Nothing left to take once the escrow has been fully filled
+    if escrow.token_a_amount == 0 {
+        return Err(EscrowErrorCode::ExceedsEscrowBalance.into());
+    }
+
+    if escrow.expiry != 0 {
+        let current_time = current_time(trailing)?;
+        if escrow.is_past_expiry(current_time) {
+            return Err(EscrowErrorCode::EscrowExpired.into());
+        }
+    }
+
+    // Fail fast instead of letting the PDA-signed transfer fail inside the token program
+    let escrow_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(escrow_token_a_ata) }?;
+    if escrow_token_a_account.is_frozen() {
+        return Err(EscrowErrorCode::FrozenAccount.into());
+    }
+
     let taker_token_a_account: &TokenAccount =
-        unsafe { TokenAccount::from_account_info_unchecked(taker_token_a_ata) }?;
-    let taker_token_b_account: &TokenAccount =
-        unsafe { TokenAccount::from_account_info_unchecked(taker_token_b_ata) }?;
+        unsafe { token_account_unchecked(taker_token_a_ata) }?;
 
     if taker_token_a_account.mint() != &escrow.token_a_mint {
         return Err(EscrowErrorCode::InvalidTokenMint.into());
     }
 
-    if taker_token_b_account.mint() != &escrow.token_b_mint {
-        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    // SOL-priced escrows are paid from the taker's wallet; the token B account slots are unused
+    let priced_in_sol = escrow.is_priced_in_sol();
+    let taker_token_b_account: Option<&TokenAccount> = if priced_in_sol {
+        None
+    } else {
+        let account = unsafe { TokenAccount::from_account_info_unchecked(taker_token_b_ata) }?;
+        if account.mint() != &escrow.token_b_mint {
+            return Err(EscrowErrorCode::InvalidTokenMint.into());
+        }
+        Some(account)
+    };
+
+    // Simple takes carry no bytes after the discriminator unless they split the payment, in
+    // which case the body is a Simple `TakeEscrowIx` with a split; other takes always need a
+    // body of one of the `TakeEscrowIx` lengths. Anything else is a client bug.
+    let take_ix = match (escrow.escrow_type, instruction_data.is_empty()) {
+        (EscrowType::Simple, true) => None,
+        (_, true) => {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        (escrow_type, false) => {
+            let ix = TakeEscrowIx::unpack(instruction_data)?;
+            if escrow_type == EscrowType::Simple
+                && (ix.escrow_type != EscrowType::Simple || ix.split_token_b_amount.is_none())
+            {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            Some(ix)
+        }
+    };
+
+    // Oracle escrows read the price feed the maker pinned at make
+    let oracle_account = match escrow.escrow_type {
+        EscrowType::Oracle => {
+            find_account(remaining(), |account| account.key() == &escrow.oracle_feed)
+        }
+        _ => None,
+    };
+    let remaining =
+        || remaining().filter(|account| Some(account.key()) != oracle_account.map(|a| a.key()));
+
+    // Escrows with a protocol fee pay it to a token B account of the recipient the maker chose;
+    // without a recipient no fee is charged
+    let fee_recipient = escrow.fee_recipient;
+    let fee_account = if escrow.fee_bps == 0 || fee_recipient == [0; 32] {
+        None
+    } else {
+        let fee_account = find_account(remaining(), |account| {
+            unsafe { TokenAccount::from_account_info_unchecked(account) }
+                .is_ok_and(|collector| collector.owner() == &fee_recipient)
+        })
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let collector = unsafe { TokenAccount::from_account_info_unchecked(fee_account) }
+            .map_err(|_| EscrowErrorCode::InvalidFeeAccount)?;
+        if collector.owner() != &fee_recipient {
+            return Err(EscrowErrorCode::InvalidFeeAccount.into());
+        }
+        if collector.mint() != &escrow.token_b_mint {
+            return Err(EscrowErrorCode::InvalidTokenMint.into());
+        }
+        Some(fee_account)
+    };
+    let remaining =
+        || remaining().filter(|account| Some(account.key()) != fee_account.map(|a| a.key()));
+
+    // Takers may split the token B payment across a second token B account
+    let split_amount = take_ix.as_ref().and_then(|ix| ix.split_token_b_amount);
+    let split_payment = match (split_amount, taker_token_b_account) {
+        (Some(_), None) => return Err(ProgramError::InvalidInstructionData),
+        (Some(amount), Some(taker_token_b_account)) => {
+            let taker_token_b_ata_2 = find_account(remaining(), |account| {
+                account.data_len() == TokenAccount::LEN
+                    && unsafe { account.owner() } == &pinocchio_token::ID
+            })
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if taker_token_b_ata_2.key() == taker_token_b_ata.key() {
+                return Err(EscrowErrorCode::DuplicateAccount.into());
+            }
+            let taker_token_b_account_2: &TokenAccount =
+                unsafe { TokenAccount::from_account_info_unchecked(taker_token_b_ata_2) }?;
+            if taker_token_b_account.owner() != taker_account.key()
+                || taker_token_b_account_2.owner() != taker_account.key()
+            {
+                return Err(EscrowErrorCode::InvalidTokenOwner.into());
+            }
+            if taker_token_b_account_2.mint() != &escrow.token_b_mint {
+                return Err(EscrowErrorCode::InvalidTokenMint.into());
+            }
+            Some((
+                taker_token_b_ata_2,
+                taker_token_b_account_2.amount(),
+                amount,
+            ))
+        }
+        (None, _) => None,
+    };
+    let taker_token_b_balance = match taker_token_b_account {
+        Some(account) => account
+            .amount()
+            .checked_add(split_payment.map_or(0, |(_, balance, _)| balance))
+            .ok_or(EscrowErrorCode::ArithmeticOverflow)?,
+        None => taker_account.lamports(),
+    };
+
+    // Pay the maker, in lamports for SOL-priced escrows, less any protocol fee (rounded down).
+    // Returns the fee.
+    let fee_bps = escrow.fee_bps;
+    let pay_maker = |amount: u64| -> Result<u64, ProgramError> {
+        if priced_in_sol {
+            SystemTransfer {
+                from: taker_account,
+                to: maker_account,
+                lamports: amount,
+            }
+            .invoke()?;
+            return Ok(0);
+        }
+
+        let balance_before = token_b_balance(taker_token_b_ata, split_payment)?;
+
+        let fee = match fee_account {
+            Some(fee_account) => {
+                let fee = (amount as u128 * fee_bps as u128 / 10_000) as u64;
+                TokenTransfer {
+                    token_program: &pinocchio_token::ID,
+                    from: taker_token_b_ata,
+                    to: fee_account,
+                    authority: taker_account,
+                    amount: fee,
+                }
+                .invoke()?;
+                fee
+            }
+            None => 0,
+        };
+        transfer_token_b(
+            taker_token_b_ata,
+            split_payment,
+            maker_token_b_ata,
+            taker_account,
+            amount - fee,
+        )?;
+
+        // The taker must be debited exactly what the program meant to charge, fee included
+        let balance_after = token_b_balance(taker_token_b_ata, split_payment)?;
+        if balance_before.checked_sub(balance_after) != Some(amount) {
+            return Err(EscrowErrorCode::TokenBChargeMismatch.into());
+        }
+        Ok(fee)
+    };
+
+    // When the maker designated a proceeds vault, token B must be delivered there; otherwise
+    // to a token B account the maker owns
+    if escrow.token_b_vault != [0; 32] {
+        if maker_token_b_ata.key() != &escrow.token_b_vault {
+            return Err(EscrowErrorCode::InvalidProceedsVault.into());
+        }
+        let token_b_vault: &TokenAccount =
+            unsafe { TokenAccount::from_account_info_unchecked(maker_token_b_ata) }?;
+        if token_b_vault.mint() != &escrow.token_b_mint {
+            return Err(EscrowErrorCode::InvalidTokenMint.into());
+        }
+    } else if !priced_in_sol {
+        let maker_token_b_account: &TokenAccount =
+            unsafe { TokenAccount::from_account_info_unchecked(maker_token_b_ata) }?;
+        if maker_token_b_account.mint() != &escrow.token_b_mint {
+            return Err(EscrowErrorCode::InvalidTokenMint.into());
+        }
+        if maker_token_b_account.owner() != &escrow.maker_pubkey {
+            return Err(EscrowErrorCode::InvalidTokenOwner.into());
+        }
     }
 
+    let token_program = escrow.token_program;
     let bump_array = [escrow.bump];
+    let escrow_seed = escrow.seed;
     let seed = [
-        Seed::from(Escrow::PREFIX.as_bytes()),
+        Seed::from(escrow.prefix()),
         Seed::from(maker_account.key()),
-        Seed::from(&escrow.seed),
+        Seed::from(&escrow_seed),
         Seed::from(&bump_array),
     ];
     let signer = Signer::from(&seed);
 
-    match escrow.escrow_type {
+    let escrow_id = Escrow::escrow_id(maker_account.key(), &escrow_seed);
+    let (filled, paid) = match escrow.escrow_type {
         EscrowType::Simple => {
-            if escrow.token_a_amount > taker_token_a_account.amount()
-                || escrow.token_b_amount > taker_token_b_account.amount()
+            // Aliased accounts would make the transfers net out
+            if escrow_token_a_ata.key() == taker_token_a_ata.key()
+                || (!priced_in_sol && taker_token_b_ata.key() == maker_token_b_ata.key())
             {
-                return Err(EscrowErrorCode::InsufficientFunds.into());
+                return Err(EscrowErrorCode::DuplicateAccount.into());
+            }
+
+            if escrow.token_a_amount > taker_token_a_account.amount() {
+                return Err(EscrowErrorCode::TakerInsufficientTokenA.into());
+            }
+            if escrow.token_b_amount > taker_token_b_balance {
+                return Err(EscrowErrorCode::TakerInsufficientTokenB.into());
             }
 
             TokenTransfer {
+                token_program: &token_program,
                 from: escrow_token_a_ata,
                 to: taker_token_a_ata,
                 authority: escrow_account,
                 amount: escrow.token_a_amount,
             }
-            .invoke_signed(&[signer.clone()])?;
+            .invoke_signed(core::slice::from_ref(&signer))?;
 
-            TokenTransfer {
-                from: taker_token_b_ata,
-                to: maker_token_b_ata,
-                authority: taker_account,
-                amount: escrow.token_b_amount,
-            }
-            .invoke()?;
+            pay_maker(escrow.token_b_amount)?;
+
+            let filled = (escrow.token_a_amount, escrow.token_b_amount);
+            escrow.token_a_amount = 0;
+            filled
         }
         // Here even if the change is not enough, we still transfer the token to the maker and reduce the escrow amount
         EscrowType::Partial => {
-            let ix = TakeEscrowIx::unpack(instruction_data)?;
+            let ix = take_ix.ok_or(ProgramError::InvalidInstructionData)?;
+            ix.verify_params_hash(escrow_account.key())?;
 
-            if ix.token_a_amount > escrow.token_a_amount {
-                return Err(EscrowErrorCode::InsufficientFunds.into());
+            if escrow.fills_enabled == 0 {
+                return Err(EscrowErrorCode::FillsDisabled.into());
             }
 
-            let percentage = (ix.token_a_amount as u64 * 10000) / escrow.token_a_amount;
-            let token_b_amount = (escrow.token_b_amount as u64 * percentage) / 10000;
+            // Zero fills would only reset the cooldown clock and log empty fills
+            if ix.token_a_amount == 0 || ix.token_a_amount > escrow.token_a_amount {
+                return Err(EscrowErrorCode::ExceedsEscrowBalance.into());
+            }
+            if escrow.all_or_nothing != 0 && ix.token_a_amount != escrow.token_a_amount {
+                return Err(EscrowErrorCode::PartialFillNotAllowed.into());
+            }
 
-            if token_b_amount > taker_token_b_account.amount() {
-                return Err(EscrowErrorCode::InsufficientFunds.into());
+            // Enforce the maker's minimum spacing between consecutive fills
+            let current_time = current_time(trailing)?;
+            if current_time < escrow.last_fill_time.saturating_add(escrow.fill_cooldown) {
+                return Err(EscrowErrorCode::CooldownActive.into());
+            }
+
+            let token_b_amount = escrow.partial_fill_token_b(ix.token_a_amount)?;
+            // Larger fills may be charged less, but the remaining ask is reduced at the base rate
+            let token_b_owed = escrow.token_b_base_units(token_b_amount)?;
+            let token_b_charged = escrow.apply_volume_discount(ix.token_a_amount, token_b_owed);
+
+            if token_b_charged > taker_token_b_balance {
+                return Err(EscrowErrorCode::TakerInsufficientTokenB.into());
             }
 
             TokenTransfer {
+                token_program: &token_program,
                 from: escrow_token_a_ata,
                 to: taker_token_a_ata,
                 authority: escrow_account,
                 amount: ix.token_a_amount,
             }
-            .invoke_signed(&[signer.clone()])?;
+            .invoke_signed(core::slice::from_ref(&signer))?;
 
-            TokenTransfer {
-                from: taker_token_b_ata,
-                to: maker_token_b_ata,
-                authority: taker_account,
-                amount: token_b_amount,
-            }
-            .invoke()?;
+            let fee = pay_maker(token_b_charged)?;
 
             escrow.token_a_amount -= ix.token_a_amount;
             escrow.token_b_amount -= token_b_amount;
+            escrow.token_b_collected = escrow
+                .token_b_collected
+                .saturating_add(token_b_charged - fee);
+            escrow.last_fill_time = current_time;
+            escrow.record_fill(*taker_account.key(), ix.token_a_amount, current_time);
+            (ix.token_a_amount, token_b_charged)
         }
         // In dutch auction, declining price mechanisms where the required amount of token B decreases over time until someone takes the offer.
+        // Takers may fill any part of the remaining lot at the current per-unit price.
         EscrowType::DutchAuction => {
-            let ix = TakeEscrowIx::unpack(instruction_data)?;
+            let ix = take_ix.ok_or(ProgramError::InvalidInstructionData)?;
+            ix.verify_params_hash(escrow_account.key())?;
 
-            if ix.token_a_amount > taker_token_a_account.amount() {
-                return Err(EscrowErrorCode::InsufficientFunds.into());
-            }
+            // Fill what remains unless the taker asked for all-or-nothing
+            let fill_amount = if ix.token_a_amount > escrow.token_a_amount {
+                if ix.fill_or_kill {
+                    return Err(EscrowErrorCode::ExceedsEscrowBalance.into());
+                }
+                escrow.token_a_amount
+            } else {
+                ix.token_a_amount
+            };
 
-            // Calculate current Dutch auction price
-            let current_time = Clock::get()?.unix_timestamp as u64;
-            let required_token_b_amount = escrow.get_required_token_b_amount(current_time);
+            // Price this fill at the current per-unit price of the original lot
+            let current_time = current_time(trailing)?;
+            if escrow.is_past_floor_deadline(current_time) {
+                return Err(EscrowErrorCode::EscrowExpired.into());
+            }
+            let required_token_b_amount = escrow.dutch_fill_cost(fill_amount, current_time)?;
 
+            // The taker's token_b_amount caps the price against decay they didn't see; they
+            // are charged only the current price
             if ix.token_b_amount < required_token_b_amount {
-                return Err(EscrowErrorCode::InsufficientFunds.into());
+                return Err(EscrowErrorCode::DutchUnderpayment.into());
             }
 
             // Transfer token A from escrow to taker
             TokenTransfer {
+                token_program: &token_program,
                 from: escrow_token_a_ata,
                 to: taker_token_a_ata,
                 authority: escrow_account,
-                amount: ix.token_a_amount,
+                amount: fill_amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            pay_maker(required_token_b_amount)?;
+
+            // The remaining quantity keeps decaying on the original schedule
+            escrow.token_a_amount -= fill_amount;
+            (fill_amount, required_token_b_amount)
+        }
+        // Oracle escrows are priced by an external feed; takers may fill any part of the lot
+        EscrowType::Oracle => {
+            let ix = take_ix.ok_or(ProgramError::InvalidInstructionData)?;
+            ix.verify_params_hash(escrow_account.key())?;
+
+            if escrow.oracle_program == [0; 32] {
+                return Err(EscrowErrorCode::InvalidEscrowType.into());
+            }
+            if ix.token_a_amount == 0 || ix.token_a_amount > escrow.token_a_amount {
+                return Err(EscrowErrorCode::ExceedsEscrowBalance.into());
+            }
+
+            let oracle_account = oracle_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let oracle_price =
+                OraclePrice::load(oracle_account, &escrow.oracle_feed, &escrow.oracle_program)?;
+            let current_time = current_time(trailing)?;
+            oracle_price.check_fresh(current_time, escrow.oracle_max_age)?;
+
+            let required_token_b_amount = oracle_price.token_b_cost(ix.token_a_amount)?;
+            if ix.token_b_amount < required_token_b_amount {
+                return Err(EscrowErrorCode::InsufficientFunds.into());
             }
-            .invoke_signed(&[signer.clone()])?;
 
             TokenTransfer {
-                from: taker_token_b_ata,
-                to: maker_token_b_ata,
-                authority: taker_account,
-                amount: required_token_b_amount,
+                token_program: &token_program,
+                from: escrow_token_a_ata,
+                to: taker_token_a_ata,
+                authority: escrow_account,
+                amount: ix.token_a_amount,
             }
-            .invoke()?;
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            pay_maker(required_token_b_amount)?;
+
+            escrow.token_a_amount -= ix.token_a_amount;
+            (ix.token_a_amount, required_token_b_amount)
         }
-        _ => {
-            return Err(EscrowErrorCode::InvalidEscrowType.into());
+        EscrowType::English => return Err(EscrowErrorCode::InvalidEscrowType.into()),
+    };
+
+    // Capture the event before a closing take wipes the escrow data
+    let event = EscrowEvent {
+        kind: EscrowEventKind::Taken,
+        escrow_type: escrow.escrow_type,
+        maker: escrow.maker_pubkey,
+        seed: escrow_seed,
+        token_a_mint: escrow.token_a_mint,
+        token_b_mint: escrow.token_b_mint,
+        token_a_amount: filled,
+        token_b_amount: paid,
+    };
+
+    if escrow.token_a_amount != 0 {
+        escrow.state = Escrow::STATE_ACTIVE;
+    }
+
+    // A take that drains the escrow completes it, so return its rent to the maker. Leave it
+    // open if stray tokens were sent to the vault; the maker can close it with a refund.
+    if escrow.token_a_amount == 0 && escrow_token_a_account.amount() == 0 {
+        close_token_account(
+            &token_program,
+            escrow_token_a_ata,
+            maker_account,
+            escrow_account,
+            &signer,
+        )?;
+        close_escrow_account(escrow_account, maker_account)?;
+    }
+
+    msg!("ESCROW_TAKEN id={} token_a_amount={}", escrow_id, filled);
+    event.emit();
+
+    Ok(())
+}
+
+/// Take with the body `take_escrow` expects for `ix.escrow_type`: Simple takes carry none
+pub(crate) fn take_with_ix(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix: &TakeEscrowIx,
+) -> ProgramResult {
+    match ix.escrow_type {
+        EscrowType::Simple => take_escrow(program_id, accounts, &[]),
+        _ => take_escrow(program_id, accounts, &ix.pack()),
+    }
+}
+
+/// The first of `accounts` matching `predicate`, or the first account when none does
+fn find_account<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo> + Clone,
+    predicate: impl Fn(&AccountInfo) -> bool,
+) -> Option<&'a AccountInfo> {
+    let first = accounts.clone().next();
+    accounts.find(|account| predicate(account)).or(first)
+}
+
+/// Pay `amount` of token B to the maker, drawing the split portion from the taker's second account
+fn transfer_token_b(
+    taker_token_b_ata: &AccountInfo,
+    split_payment: Option<(&AccountInfo, u64, u64)>,
+    maker_token_b_ata: &AccountInfo,
+    taker_account: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let split_amount = split_payment.map_or(0, |(_, _, split_amount)| split_amount);
+    if split_amount > amount {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    TokenTransfer {
+        token_program: &pinocchio_token::ID,
+        from: taker_token_b_ata,
+        to: maker_token_b_ata,
+        authority: taker_account,
+        amount: amount - split_amount,
+    }
+    .invoke()?;
+
+    if let Some((taker_token_b_ata_2, _, split_amount)) = split_payment {
+        TokenTransfer {
+            token_program: &pinocchio_token::ID,
+            from: taker_token_b_ata_2,
+            to: maker_token_b_ata,
+            authority: taker_account,
+            amount: split_amount,
         }
+        .invoke()?;
     }
 
     Ok(())
 }
 
+/// Combined token B balance of the taker's primary and optional split payment accounts
+fn token_b_balance(
+    taker_token_b_ata: &AccountInfo,
+    split_payment: Option<(&AccountInfo, u64, u64)>,
+) -> Result<u64, ProgramError> {
+    let mut balance =
+        unsafe { TokenAccount::from_account_info_unchecked(taker_token_b_ata) }?.amount();
+    if let Some((taker_token_b_ata_2, _, _)) = split_payment {
+        balance = balance
+            .checked_add(
+                unsafe { TokenAccount::from_account_info_unchecked(taker_token_b_ata_2) }?.amount(),
+            )
+            .ok_or(EscrowErrorCode::ArithmeticOverflow)?;
+    }
+    Ok(balance)
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct TakeEscrowIx {
     pub escrow_type: EscrowType,
     pub token_a_amount: u64,
+    // Dutch auction and Oracle takes: the most token B the taker will pay; only the current
+    // price is charged
     pub token_b_amount: u64,
+    // Optional taker commitment over the escrow key and every other field; see `hash_params`
+    pub params_hash: Option<[u8; 32]>,
+    // Optional portion of the token B payment drawn from the taker's second token B account
+    pub split_token_b_amount: Option<u64>,
+    // Dutch auction takes: reject instead of filling less than token_a_amount
+    pub fill_or_kill: bool,
 }
 
 impl TakeEscrowIx {
     pub const LEN: usize = 1 + 8 + 8;
+    pub const COMMITTED_LEN: usize = Self::LEN + 32;
+    pub const SPLIT_LEN: usize = Self::LEN + 8;
+    pub const SPLIT_COMMITTED_LEN: usize = Self::SPLIT_LEN + 32;
+    /// Base params, flags, split amount (0 = none) and params hash (zeroed = none)
+    pub const EXTENDED_LEN: usize = Self::LEN + 1 + 8 + 32;
+
+    pub const FLAG_FILL_OR_KILL: u8 = 1;
 
     pub fn new(escrow_type: EscrowType, token_a_amount: u64, token_b_amount: u64) -> Self {
         Self {
             escrow_type,
             token_a_amount,
             token_b_amount,
+            params_hash: None,
+            split_token_b_amount: None,
+            fill_or_kill: false,
+        }
+    }
+
+    /// sha256(escrow key || `pack_extended()` minus the hash), so the amounts, flags and split
+    /// amount are all committed
+    pub fn hash_params(&self, escrow: &Pubkey) -> [u8; 32] {
+        hashv(&[escrow, &self.pack_extended()[..Self::LEN + 9]])
+    }
+
+    /// Reject the take if the taker committed to different parameters than were submitted
+    pub fn verify_params_hash(&self, escrow: &Pubkey) -> ProgramResult {
+        if let Some(params_hash) = self.params_hash {
+            if self.hash_params(escrow) != params_hash {
+                return Err(EscrowErrorCode::ParamsHashMismatch.into());
+            }
         }
+        Ok(())
     }
 
     pub fn pack(&self) -> [u8; Self::LEN] {
@@ -186,15 +657,64 @@ impl TakeEscrowIx {
         data
     }
 
+    /// Pack every optional field using the extended layout
+    pub fn pack_extended(&self) -> [u8; Self::EXTENDED_LEN] {
+        let mut data = [0u8; Self::EXTENDED_LEN];
+        data[..Self::LEN].copy_from_slice(&self.pack());
+        if self.fill_or_kill {
+            data[Self::LEN] |= Self::FLAG_FILL_OR_KILL;
+        }
+        data[Self::LEN + 1..Self::LEN + 9]
+            .copy_from_slice(&self.split_token_b_amount.unwrap_or(0).to_le_bytes());
+        data[Self::LEN + 9..].copy_from_slice(&self.params_hash.unwrap_or([0; 32]));
+        data
+    }
+
     pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidInstructionData);
+        if data.len() == Self::EXTENDED_LEN {
+            return Self::unpack_extended(data);
         }
 
+        let (split_token_b_amount, params_hash) = match data.len() {
+            Self::LEN => (None, None),
+            Self::COMMITTED_LEN => (None, Some(data[Self::LEN..].try_into().unwrap())),
+            Self::SPLIT_LEN => (
+                Some(u64::from_le_bytes(data[Self::LEN..].try_into().unwrap())),
+                None,
+            ),
+            Self::SPLIT_COMMITTED_LEN => (
+                Some(u64::from_le_bytes(
+                    data[Self::LEN..Self::SPLIT_LEN].try_into().unwrap(),
+                )),
+                Some(data[Self::SPLIT_LEN..].try_into().unwrap()),
+            ),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
         Ok(Self {
             escrow_type: EscrowType::try_from(data[0])?,
             token_a_amount: u64::from_le_bytes(data[1..9].try_into().unwrap()),
             token_b_amount: u64::from_le_bytes(data[9..17].try_into().unwrap()),
+            params_hash,
+            split_token_b_amount,
+            fill_or_kill: false,
         })
     }
+
+    fn unpack_extended(data: &[u8]) -> Result<Self, ProgramError> {
+        let flags = data[Self::LEN];
+        if flags & !Self::FLAG_FILL_OR_KILL != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let split_token_b_amount =
+            u64::from_le_bytes(data[Self::LEN + 1..Self::LEN + 9].try_into().unwrap());
+        let params_hash: [u8; 32] = data[Self::LEN + 9..].try_into().unwrap();
+
+        let mut ix = Self::unpack(&data[..Self::LEN])?;
+        ix.fill_or_kill = flags & Self::FLAG_FILL_OR_KILL != 0;
+        ix.split_token_b_amount = (split_token_b_amount != 0).then_some(split_token_b_amount);
+        ix.params_hash = (params_hash != [0; 32]).then_some(params_hash);
+        Ok(ix)
+    }
 }
@@ -0,0 +1,121 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    instructions::{take_with_ix, TakeEscrowIx},
+    states::{try_from_account_info, Escrow, EscrowType},
+};
+
+/// Maximum number of partial takes performed by a single batch take
+pub const MAX_TAKE_BATCH_ENTRIES: usize = 4;
+
+/// Accounts passed per escrow: escrow, escrow token A account, maker, maker token B account and
+/// fee collector, which escrows without a protocol fee ignore
+const ESCROW_ACCOUNTS: usize = 5;
+
+/// Take from several Partial escrows in one instruction.
+/// Data is a list of `TakeBatchEntry`s; accounts after the token program are the escrow
+/// account groups the entries index into. Each entry is taken in order exactly as
+/// `take_escrow` would, unless it would charge more than the entry's `max_token_b`, and any
+/// invalid entry fails the whole instruction.
+pub fn take_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [taker_account, taker_token_a_ata, taker_token_b_ata, config_account, _token_program, escrow_accounts @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let entries = instruction_data.chunks_exact(TakeBatchEntry::LEN);
+    if instruction_data.is_empty() || !entries.remainder().is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if entries.len() > MAX_TAKE_BATCH_ENTRIES {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let groups = escrow_accounts.chunks_exact(ESCROW_ACCOUNTS);
+    if !groups.remainder().is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    for entry in entries {
+        let entry = TakeBatchEntry::unpack(entry)?;
+        let Some(
+            [escrow_account, escrow_token_a_ata, maker_account, maker_token_b_ata, fee_account],
+        ) = groups.clone().nth(entry.escrow_index as usize)
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if unsafe { escrow_account.owner() } != &crate::ID {
+            return Err(EscrowErrorCode::InvalidEscrowOwner.into());
+        }
+        let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+        if escrow.escrow_type != EscrowType::Partial {
+            return Err(EscrowErrorCode::InvalidEscrowType.into());
+        }
+        // Fills the escrow would refuse anyway are left for `take_escrow` to report
+        let cost = escrow.quote_fill(entry.token_a_amount, current_time)?;
+        if cost.is_some_and(|cost| cost > entry.max_token_b) {
+            return Err(EscrowErrorCode::LimitPriceExceeded.into());
+        }
+
+        let take_accounts = [
+            escrow_account.clone(),
+            escrow_token_a_ata.clone(),
+            maker_account.clone(),
+            maker_token_b_ata.clone(),
+            taker_account.clone(),
+            taker_token_a_ata.clone(),
+            taker_token_b_ata.clone(),
+            config_account.clone(),
+            fee_account.clone(),
+        ];
+        let take_ix = TakeEscrowIx::new(EscrowType::Partial, entry.token_a_amount, 0);
+        take_with_ix(program_id, &take_accounts, &take_ix)?;
+    }
+
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakeBatchEntry {
+    pub escrow_index: u8,    // Which escrow account group to take from
+    pub token_a_amount: u64, // Token A to take from that escrow
+    pub max_token_b: u64,    // Most token B the taker will pay for that fill
+}
+
+impl TakeBatchEntry {
+    pub const LEN: usize = 1 + 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = self.escrow_index;
+        data[1..9].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[9..17].copy_from_slice(&self.max_token_b.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            escrow_index: data[0],
+            token_a_amount: u64::from_le_bytes(data[1..9].try_into().unwrap()),
+            max_token_b: u64::from_le_bytes(data[9..17].try_into().unwrap()),
+        })
+    }
+}
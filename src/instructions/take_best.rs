@@ -0,0 +1,144 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    instructions::{take_with_ix, TakeEscrowIx},
+    states::{try_from_account_info, Config, Escrow},
+    token::token_account_unchecked,
+};
+
+/// Maximum number of candidate escrows compared by a single best-price take
+pub const MAX_TAKE_BEST_CANDIDATES: usize = 4;
+
+/// Accounts passed per candidate: escrow, escrow token A account, maker, maker token B account
+/// and fee collector. The fee collector is a token B account of the escrow's `fee_recipient`;
+/// escrows that charge no fee ignore it, so any account can fill the slot.
+const CANDIDATE_ACCOUNTS: usize = 5;
+
+/// Fill `token_a_amount` from whichever candidate escrow is currently cheapest.
+/// All candidates must trade the same mint pair; offers `take_escrow` would refuse are passed
+/// over, the chosen escrow is taken exactly as `take_escrow` would, and the taker never pays
+/// more than `max_token_b_amount`.
+pub fn take_best(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [taker_account, taker_token_a_ata, taker_token_b_ata, config_account, _token_program, candidates @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = TakeBestIx::unpack(instruction_data)?;
+
+    let candidates = candidates.chunks_exact(CANDIDATE_ACCOUNTS);
+    if candidates.len() == 0 || !candidates.remainder().is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if candidates.len() > MAX_TAKE_BEST_CANDIDATES {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Paused takes would refuse every candidate alike
+    if let Some(config) = Config::load(config_account)? {
+        if config.takes_paused != 0 {
+            return Err(EscrowErrorCode::ProgramPaused.into());
+        }
+    }
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+
+    let mut mints: Option<([u8; 32], [u8; 32])> = None;
+    let mut best: Option<(&[AccountInfo], TakeEscrowIx)> = None;
+    for candidate in candidates {
+        let escrow_account = &candidate[0];
+        if unsafe { escrow_account.owner() } != &crate::ID {
+            return Err(EscrowErrorCode::InvalidEscrowOwner.into());
+        }
+        let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+        let pair = (escrow.token_a_mint, escrow.token_b_mint);
+        if *mints.get_or_insert(pair) != pair {
+            return Err(EscrowErrorCode::MintMismatch.into());
+        }
+
+        // Escrows reserved for another counterparty, or the taker's own Simple and Dutch
+        // escrows, aren't on offer to this taker
+        if !escrow.allows_taker(taker_account.key()) || escrow.is_self_take(taker_account.key()) {
+            continue;
+        }
+        // A frozen vault can't pay out token A
+        if unsafe { token_account_unchecked(&candidate[1]) }?.is_frozen() {
+            continue;
+        }
+        // Also passes over Partial escrows cooling down or with fills disabled
+        let Some(cost) = escrow.quote_fill(ix_data.token_a_amount, current_time)? else {
+            continue;
+        };
+        if cost > ix_data.max_token_b_amount {
+            continue;
+        }
+        let cheapest = match &best {
+            Some((_, best_ix)) => cost < best_ix.token_b_amount,
+            None => true,
+        };
+        if cheapest {
+            best = Some((
+                candidate,
+                TakeEscrowIx::new(escrow.escrow_type, ix_data.token_a_amount, cost),
+            ));
+        }
+    }
+
+    let Some((candidate, take_ix)) = best else {
+        return Err(EscrowErrorCode::NoMatchingEscrow.into());
+    };
+
+    let take_accounts = [
+        candidate[0].clone(),
+        candidate[1].clone(),
+        candidate[2].clone(),
+        candidate[3].clone(),
+        taker_account.clone(),
+        taker_token_a_ata.clone(),
+        taker_token_b_ata.clone(),
+        config_account.clone(),
+        candidate[4].clone(),
+    ];
+    take_with_ix(program_id, &take_accounts, &take_ix)
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakeBestIx {
+    pub token_a_amount: u64,
+    pub max_token_b_amount: u64, // Most token B the taker will pay for the fill
+}
+
+impl TakeBestIx {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..8].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[8..16].copy_from_slice(&self.max_token_b_amount.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            token_a_amount: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            max_token_b_amount: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    instructions::{take_with_ix, TakeEscrowIx},
+    states::{try_from_account_info, Escrow},
+};
+
+/// Limit prices are token B base units per token A base unit, scaled by this factor
+pub const LIMIT_PRICE_SCALE: u64 = 1_000_000_000;
+
+/// Fill up to `max_token_a` of an escrow, but only if the fill costs no more than
+/// `limit_price_per_a` per unit of token A. Takes the same accounts as `take_escrow`.
+pub fn take_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let Some(escrow_account) = accounts.first() else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = TakeLimitIx::unpack(instruction_data)?;
+
+    if unsafe { escrow_account.owner() } != &crate::ID {
+        return Err(EscrowErrorCode::InvalidEscrowOwner.into());
+    }
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+    // Per-unit prices never improve on smaller fills, so take as much as the taker allows
+    let fill_amount = ix_data.max_token_a.min(escrow.token_a_amount);
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    let Some(cost) = escrow.quote_fill(fill_amount, current_time)? else {
+        return Err(EscrowErrorCode::NoMatchingEscrow.into());
+    };
+
+    if cost as u128 * LIMIT_PRICE_SCALE as u128
+        > fill_amount as u128 * ix_data.limit_price_per_a as u128
+    {
+        return Err(EscrowErrorCode::LimitPriceExceeded.into());
+    }
+
+    let take_ix = TakeEscrowIx::new(escrow.escrow_type, fill_amount, cost);
+    take_with_ix(program_id, accounts, &take_ix)
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakeLimitIx {
+    pub max_token_a: u64,       // Most token A the taker wants
+    pub limit_price_per_a: u64, // Highest token B per token A, scaled by LIMIT_PRICE_SCALE
+}
+
+impl TakeLimitIx {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..8].copy_from_slice(&self.max_token_a.to_le_bytes());
+        data[8..16].copy_from_slice(&self.limit_price_per_a.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let ix = Self {
+            max_token_a: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            limit_price_per_a: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        };
+        if ix.max_token_a == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(ix)
+    }
+}
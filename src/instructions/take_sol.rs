@@ -0,0 +1,85 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::Create as CreateAssociatedTokenAccount;
+use pinocchio_pubkey::pubkey;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+use pinocchio_token::instructions::{CloseAccount, SyncNative};
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::take_escrow,
+    states::{try_from_account_info, Escrow},
+};
+
+/// Wrapped SOL mint
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Take an escrow priced in wrapped SOL, paying from the taker's native SOL.
+/// A temporary wSOL associated token account is created for the taker, funded with
+/// `wrap_lamports`, used as the token B account for the take and closed afterwards,
+/// returning its rent and any unspent SOL to the taker. Accounts after the associated token
+/// program are passed on to the take.
+pub fn take_escrow_with_sol(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, _escrow_token_a_ata, _maker_account, _maker_token_b_ata, taker_account, _taker_token_a_ata, taker_wsol_ata, _config_account, native_mint, system_program, token_program, _associated_token_program, remaining @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (wrap_lamports, take_data) = instruction_data.split_at(8);
+    let wrap_lamports = u64::from_le_bytes(wrap_lamports.try_into().unwrap());
+
+    if native_mint.key() != &NATIVE_MINT {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+    if unsafe { escrow_account.owner() } != &crate::ID {
+        return Err(EscrowErrorCode::InvalidEscrowOwner.into());
+    }
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+    if escrow.token_b_mint != NATIVE_MINT {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    // Fails if the taker already has a wSOL account, which this take must not close
+    CreateAssociatedTokenAccount {
+        funding_account: taker_account,
+        account: taker_wsol_ata,
+        wallet: taker_account,
+        mint: native_mint,
+        system_program,
+        token_program,
+    }
+    .invoke()?;
+
+    SystemTransfer {
+        from: taker_account,
+        to: taker_wsol_ata,
+        lamports: wrap_lamports,
+    }
+    .invoke()?;
+
+    SyncNative {
+        native_token: taker_wsol_ata,
+    }
+    .invoke()?;
+
+    // The take locates fee collectors and the clock among the accounts after its own
+    let mut take_accounts = accounts[..8].to_vec();
+    take_accounts.extend_from_slice(remaining);
+    take_escrow(program_id, &take_accounts, take_data)?;
+
+    CloseAccount {
+        account: taker_wsol_ata,
+        destination: taker_account,
+        authority: taker_account,
+    }
+    .invoke()
+}
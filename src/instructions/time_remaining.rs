@@ -0,0 +1,40 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::{sysvar_unavailable, EscrowErrorCode},
+    states::{try_from_account_info, Escrow, EscrowType},
+};
+
+/// Writes the seconds left on a Dutch auction, or of an English auction's bidding window, to
+/// return data as a little-endian u64. An auction that has already ended reports zero.
+pub fn time_remaining(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [escrow_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let escrow = unsafe { try_from_account_info::<Escrow>(escrow_account) }?;
+
+    let end_time = match escrow.escrow_type {
+        EscrowType::DutchAuction => escrow.end_time,
+        EscrowType::English => escrow.auction_end,
+        _ => return Err(EscrowErrorCode::InvalidEscrowType.into()),
+    };
+
+    let current_time = Clock::get().map_err(sysvar_unavailable)?.unix_timestamp as u64;
+    let remaining = end_time.saturating_sub(current_time);
+
+    set_return_data(&remaining.to_le_bytes());
+
+    Ok(())
+}
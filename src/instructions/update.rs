@@ -0,0 +1,99 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::{load_maker_escrow, MAX_AUCTION_DURATION},
+    states::{Config, EscrowType},
+};
+
+/// Change an open escrow's asking price in place instead of cancelling and recreating it.
+/// Dutch auctions take a new start price, end price and duration; the auction keeps its start
+/// time, so the new schedule runs from when it was made.
+pub fn update_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, escrow_account, config_account, _remaing @ ..] = &accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = UpdateEscrowIx::unpack(instruction_data)?;
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if ix_data.token_b_amount == 0 {
+        return Err(EscrowErrorCode::InvalidPriceRange.into());
+    }
+
+    match escrow.escrow_type {
+        EscrowType::Simple | EscrowType::Partial => {
+            escrow.token_b_amount = ix_data.token_b_amount;
+        }
+        EscrowType::DutchAuction => {
+            if ix_data.end_price > ix_data.token_b_amount {
+                return Err(EscrowErrorCode::InvalidAuctionParams.into());
+            }
+            if ix_data.duration > MAX_AUCTION_DURATION {
+                return Err(EscrowErrorCode::InvalidAuctionParams.into());
+            }
+            if let Some(config) = Config::load(config_account)? {
+                if config.max_auction_duration != 0
+                    && ix_data.duration > config.max_auction_duration
+                {
+                    return Err(EscrowErrorCode::InvalidDuration.into());
+                }
+            }
+            let end_time = escrow
+                .start_time
+                .checked_add(ix_data.duration)
+                .ok_or(EscrowErrorCode::InvalidAuctionParams)?;
+
+            escrow.start_price = ix_data.token_b_amount;
+            escrow.token_b_amount = ix_data.token_b_amount;
+            escrow.end_price = ix_data.end_price;
+            escrow.duration = ix_data.duration;
+            escrow.end_time = end_time;
+        }
+        // Oracle escrows are priced by their feed
+        EscrowType::Oracle | EscrowType::English => {
+            return Err(EscrowErrorCode::InvalidEscrowType.into())
+        }
+    }
+
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateEscrowIx {
+    pub token_b_amount: u64, // New ask; the start price for Dutch auctions
+    // Dutch auction specific fields, ignored for other escrow types
+    pub end_price: u64,
+    pub duration: u64,
+}
+
+impl UpdateEscrowIx {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..8].copy_from_slice(&self.token_b_amount.to_le_bytes());
+        data[8..16].copy_from_slice(&self.end_price.to_le_bytes());
+        data[16..24].copy_from_slice(&self.duration.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            token_b_amount: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            end_price: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            duration: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+        })
+    }
+}
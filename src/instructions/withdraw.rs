@@ -0,0 +1,88 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    error::EscrowErrorCode,
+    instructions::{check_proceeds_floor, load_maker_escrow},
+    states::EscrowType,
+    token::{token_account_unchecked, Transfer as TokenTransfer},
+};
+
+/// Pull part of a partial escrow's remaining token A back to the maker.
+/// The remaining token B ask shrinks by the same proportion, so the price is unchanged.
+pub fn withdraw_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [maker_account, maker_token_a_ata, escrow_account, escrow_token_a_ata, _token_program, _remaing @ ..] =
+        &accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let withdraw_amount = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    let escrow = load_maker_escrow(maker_account, escrow_account)?;
+
+    if escrow.escrow_type != EscrowType::Partial {
+        return Err(EscrowErrorCode::InvalidEscrowType.into());
+    }
+
+    check_proceeds_floor(escrow)?;
+
+    if withdraw_amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if withdraw_amount > escrow.token_a_amount {
+        return Err(EscrowErrorCode::InsufficientFunds.into());
+    }
+
+    // Token A may only leave through the vault the make funded
+    if escrow_token_a_ata.key() != &escrow.vault {
+        return Err(EscrowErrorCode::InvalidEscrowVault.into());
+    }
+
+    let escrow_token_a_account: &TokenAccount =
+        unsafe { token_account_unchecked(escrow_token_a_ata) }?;
+    if escrow_token_a_account.mint() != &escrow.token_a_mint {
+        return Err(EscrowErrorCode::InvalidTokenMint.into());
+    }
+
+    let token_b_reduction = (escrow.token_b_amount as u128 * withdraw_amount as u128
+        / escrow.token_a_amount as u128) as u64;
+
+    let escrow_seed = escrow.seed;
+    let bump_array = [escrow.bump];
+    let seed = [
+        Seed::from(escrow.prefix()),
+        Seed::from(maker_account.key()),
+        Seed::from(&escrow_seed),
+        Seed::from(&bump_array),
+    ];
+    let signer = Signer::from(&seed);
+
+    TokenTransfer {
+        token_program: &escrow.token_program,
+        from: escrow_token_a_ata,
+        to: maker_token_a_ata,
+        authority: escrow_account,
+        amount: withdraw_amount,
+    }
+    .invoke_signed(&[signer])?;
+
+    escrow.token_a_amount -= withdraw_amount;
+    escrow.token_b_amount -= token_b_reduction;
+
+    Ok(())
+}
@@ -4,11 +4,23 @@ use pinocchio::{
 };
 use pinocchio_pubkey::pubkey;
 
-use crate::instructions::{make_escrow, take_escrow};
+use crate::instructions::{
+    batch_cancel, batch_status, cancel_escrow, check_escrow, close_escrow, deposit_escrow,
+    escrow_status, fill_history, finalize_escrow, make_escrow, make_escrow_authorized, place_bid,
+    quote, reclaim_expired, replace_escrow, reprice_dutch, set_config, set_fills_enabled,
+    settle_auction, sweep_escrow, take_batch, take_best, take_escrow, take_escrow_with_sol,
+    take_limit, time_remaining, update_escrow, withdraw_escrow, withdraw_fees,
+};
 
+#[cfg(feature = "client")]
+pub mod client;
+pub mod clock;
+pub mod close;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod states;
+pub mod token;
 
 pub const ID: Pubkey = pubkey!("N9BuK6SmDXHr2jpca1C4WzMhok2wki8sx2osK1sTobc");
 
@@ -31,6 +43,114 @@ fn process_instruction(
             msg!("Taking escrow");
             take_escrow(program_id, accounts, data)?;
         }
+        0x03 => {
+            msg!("Canceling escrow");
+            cancel_escrow(program_id, accounts, data)?;
+        }
+        0x04 => {
+            msg!("Closing escrow");
+            close_escrow(program_id, accounts, data)?;
+        }
+        0x05 => {
+            msg!("Updating escrow");
+            update_escrow(program_id, accounts, data)?;
+        }
+        0x06 => {
+            msg!("Placing bid");
+            place_bid(program_id, accounts, data)?;
+        }
+        0x07 => {
+            msg!("Depositing into escrow");
+            deposit_escrow(program_id, accounts, data)?;
+        }
+        0x08 => {
+            msg!("Quoting escrow");
+            quote(program_id, accounts, data)?;
+        }
+        0x09 => {
+            msg!("Taking escrows in a batch");
+            take_batch(program_id, accounts, data)?;
+        }
+        0x0A => {
+            msg!("Sweeping escrow token B");
+            sweep_escrow(program_id, accounts, data)?;
+        }
+        0x0B => {
+            msg!("Reclaiming expired escrow");
+            reclaim_expired(program_id, accounts, data)?;
+        }
+        0x0C => {
+            msg!("Querying escrow status");
+            escrow_status(program_id, accounts, data)?;
+        }
+        0x0D => {
+            msg!("Reading fill history");
+            fill_history(program_id, accounts, data)?;
+        }
+        0x0E => {
+            msg!("Replacing escrow");
+            replace_escrow(program_id, accounts, data)?;
+        }
+        0x0F => {
+            msg!("Setting config");
+            set_config(program_id, accounts, data)?;
+        }
+        0x10 => {
+            msg!("Making escrow with maker authorization");
+            make_escrow_authorized(program_id, accounts, data)?;
+        }
+        0x11 => {
+            msg!("Reading auction time remaining");
+            time_remaining(program_id, accounts, data)?;
+        }
+        0x12 => {
+            msg!("Querying batch escrow status");
+            batch_status(program_id, accounts, data)?;
+        }
+        0x13 => {
+            msg!("Withdrawing from escrow");
+            withdraw_escrow(program_id, accounts, data)?;
+        }
+        0x14 => {
+            msg!("Canceling escrows in batch");
+            batch_cancel(program_id, accounts, data)?;
+        }
+        0x15 => {
+            msg!("Taking best priced escrow");
+            take_best(program_id, accounts, data)?;
+        }
+        0x16 => {
+            msg!("Finalizing escrow");
+            finalize_escrow(program_id, accounts, data)?;
+        }
+        0x17 => {
+            msg!("Taking escrow with native SOL");
+            take_escrow_with_sol(program_id, accounts, data)?;
+        }
+        0x18 => {
+            msg!("Taking escrow at a limit price");
+            take_limit(program_id, accounts, data)?;
+        }
+        0x19 => {
+            msg!("Checking escrow consistency");
+            check_escrow(program_id, accounts, data)?;
+        }
+        0x1A => {
+            msg!("Setting escrow fills enabled");
+            set_fills_enabled(program_id, accounts, data)?;
+        }
+        0x1B => {
+            msg!("Repricing Dutch auction");
+            reprice_dutch(program_id, accounts, data)?;
+        }
+        0x1C => {
+            msg!("Settling auction");
+            settle_auction(program_id, accounts, data)?;
+        }
+        0x1D => {
+            msg!("Withdrawing protocol fees");
+            withdraw_fees(program_id, accounts, data)?;
+        }
         _ => {
             return Err(ProgramError::InvalidInstructionData);
         }
@@ -0,0 +1,59 @@
+use crate::error::EscrowErrorCode;
+use crate::states::{try_from_account_info, DataLen};
+use pinocchio::account_info::AccountInfo;
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use pinocchio_pubkey::pubkey;
+
+/// Maximum number of token B mints the program-level allowlist can hold
+pub const MAX_ALLOWED_TOKEN_B_MINTS: usize = 8;
+
+/// Program-wide settings, stored in a singleton PDA controlled by an admin
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub admin: [u8; 32],
+    pub bump: u8,
+    // Token B mint allowlist (only enforced when enabled)
+    pub allowlist_enabled: u8,
+    pub allowed_token_b_mint_count: u8,
+    pub allowed_token_b_mints: [[u8; 32]; MAX_ALLOWED_TOKEN_B_MINTS],
+    // Incident response: halts takes while makes and cancels keep working
+    pub takes_paused: u8,
+    // Longest Dutch auction makers may open, in seconds (0 = unlimited)
+    pub max_auction_duration: u64,
+}
+
+impl DataLen for Config {
+    const LEN: usize = core::mem::size_of::<Self>();
+}
+
+impl Config {
+    pub const PREFIX: &'static str = "Config";
+
+    /// The config PDA, derived from `PREFIX` and the program ID ahead of time so takes and
+    /// makes don't search for its bump on every call
+    pub const ADDRESS: Pubkey = pubkey!("EvTw5A9DqwNPkZ48zDeTt9q8jtE3yF5fBqsGFAJ4Kjf4");
+
+    /// Canonical bump of `ADDRESS`
+    pub const BUMP: u8 = 255;
+
+    /// Load the program config, or `None` if it has not been initialized yet
+    pub fn load(config_account: &AccountInfo) -> Result<Option<&Self>, ProgramError> {
+        if config_account.key() != &Self::ADDRESS {
+            return Err(EscrowErrorCode::PdaMismatch.into());
+        }
+        if config_account.data_is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(unsafe {
+            try_from_account_info::<Self>(config_account)
+        }?))
+    }
+
+    /// Whether escrows may be priced in `token_b_mint`
+    pub fn is_token_b_mint_allowed(&self, token_b_mint: &Pubkey) -> bool {
+        self.allowlist_enabled == 0
+            || self.allowed_token_b_mints[..self.allowed_token_b_mint_count as usize]
+                .contains(token_b_mint)
+    }
+}
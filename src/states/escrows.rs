@@ -1,6 +1,7 @@
 use crate::error::EscrowErrorCode;
 use crate::instructions::MakeEscrowIx;
 use crate::states::{try_from_account_info_mut, DataLen};
+use core::mem::offset_of;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::{msg, ProgramResult};
 use pinocchio::{program_error::ProgramError, pubkey, pubkey::Pubkey};
@@ -11,6 +12,7 @@ pub enum EscrowType {
     Partial = 1,
     DutchAuction = 2,
     Oracle = 3,
+    English = 4,
 }
 
 impl TryFrom<u8> for EscrowType {
@@ -22,16 +24,89 @@ impl TryFrom<u8> for EscrowType {
             1 => Self::Partial,
             2 => Self::DutchAuction,
             3 => Self::Oracle,
+            4 => Self::English,
             _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 }
 
+/// Compact lifecycle status reported by the status query instruction
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Open = 0,
+    PartiallyFilled = 1,
+    Closed = 2,
+    Expired = 3,
+}
+
+/// Why an escrow reached its terminal state, reported in the `ESCROW_CLOSED` event
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    Filled = 0,
+    Expired = 1,
+}
+
+/// Number of most recent partial fills kept in an escrow's fill history
+pub const FILL_HISTORY_LEN: usize = 8;
+
+/// A single partial fill recorded for audit purposes
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FillRecord {
+    pub taker: [u8; 32],
+    pub token_a_amount: u64,
+    pub timestamp: u64,
+}
+
+impl FillRecord {
+    pub const LEN: usize = 32 + 8 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..32].copy_from_slice(&self.taker);
+        data[32..40].copy_from_slice(&self.token_a_amount.to_le_bytes());
+        data[40..48].copy_from_slice(&self.timestamp.to_le_bytes());
+        data
+    }
+}
+
+/// Token B "mint" of escrows priced in native SOL: the system program id. Takers of these
+/// escrows pay the maker lamports directly instead of transferring an SPL token.
+pub const SOL_TOKEN_B_MINT: Pubkey = [0; 32];
+
+/// Decimals of native SOL, recorded as token B decimals on SOL-priced escrows
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Fixed-point scale of `Escrow::normalized_price`
+pub const NORMALIZED_PRICE_SCALE: u64 = 1_000_000_000;
+
+/// Length of the maker-chosen seed that, with the maker, derives an escrow's address.
+/// Makers may use it as a nonce such as an order id.
+pub const ESCROW_SEED_LEN: usize = 8;
+
+/// Values `Escrow::initialize` records that don't come from the make instruction data
+#[derive(Debug, Clone, Copy)]
+pub struct EscrowInit {
+    pub maker_pubkey: [u8; 32],
+    pub token_a_mint: [u8; 32],
+    pub token_b_mint: [u8; 32],
+    // Auction timing; zero for other escrow types
+    pub start_time: u64,
+    pub end_time: u64,
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+    // The escrow's token A account and the token program that owns it
+    pub vault: [u8; 32],
+    pub token_program: [u8; 32],
+}
+
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Escrow {
     pub maker_pubkey: [u8; 32],
-    pub seed: [u8; 2],
+    pub seed: [u8; ESCROW_SEED_LEN],
     pub escrow_type: EscrowType,
     pub token_a_mint: [u8; 32],
     pub token_a_amount: u64,
@@ -44,22 +119,118 @@ pub struct Escrow {
     pub start_time: u64,  // Auction start timestamp (set by program)
     pub duration: u64,    // Auction duration in seconds (user input)
     pub end_time: u64,    // Auction end timestamp (computed as start_time + duration)
+    // Partial escrow specific fields
+    pub fill_cooldown: u64, // Minimum seconds between consecutive partial fills
+    pub last_fill_time: u64, // Timestamp of the most recent partial fill
+    // Lifecycle tracking
+    pub original_token_a_amount: u64, // Token A amount escrowed at creation
+    // Partial fill audit trail (ring buffer indexed by fill_count % FILL_HISTORY_LEN)
+    pub fill_count: u64,
+    pub fill_history: [FillRecord; FILL_HISTORY_LEN],
+    // Token B delivery
+    pub token_b_vault: [u8; 32], // Token account receiving proceeds (zero = maker's ATA)
+    // Partial escrow volume discount
+    pub discount_threshold: u64, // Partial takes above this token A amount get the discount (0 = disabled)
+    pub volume_discount_bps: u16, // Discount on token B charged, in basis points
+    // Mint decimals captured at make, used by decimals-normalized pricing
+    pub price_mode: u8, // MakeEscrowIx::PRICE_MODE_TOTAL or PRICE_MODE_DECIMALS_NORMALIZED
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+    // Token A refunds
+    pub refund_account: [u8; 32], // Token account receiving refunds (zero = maker's token A account)
+    // Partial escrow fill switch
+    pub fills_enabled: u8, // Takes are rejected while the maker has fills disabled (0)
+    // Partial escrow proceeds floor: token A stays locked until takers have paid the floor
+    pub proceeds_floor: u64, // Minimum total token B the maker must collect (0 = disabled)
+    pub proceeds_deadline: u64, // Timestamp after which the floor no longer locks token A
+    pub token_b_collected: u64, // Total token B paid to the maker by takes so far
+    // Oracle escrow price feed
+    pub oracle_program: [u8; 32], // Program that must own the price feed account
+    pub oracle_max_age: u64,      // Oldest price accepted, in seconds
+    // Offer lifetime
+    pub expiry: u64, // Timestamp after which takes are rejected (0 = no expiry)
+    // English auction specific fields; token_b_amount is the reserve price
+    pub highest_bid: u64, // Token B held for the leading bid (0 = no bids)
+    pub highest_bidder: [u8; 32], // Wallet of the leading bidder
+    pub auction_end: u64, // Timestamp after which bids close and the auction settles
+    // Protocol fee
+    pub fee_bps: u16, // Share of token B paid to the fee recipient on Simple and Partial takes
+    // Lifecycle
+    pub state: u8, // Escrow::STATE_ACTIVE until a take completes or a cancel closes the escrow
+    // Private escrows
+    pub allowed_taker: [u8; 32], // Only wallet allowed to take (zero = anyone)
+    // Token A custody
+    pub vault: [u8; 32], // Token A account funded at make; takes must draw from it
+    pub token_program: [u8; 32], // Token program of token A: the classic program or Token-2022
+    // Dutch auction floor
+    pub expire_at_floor: u8, // Takes are rejected from end_time instead of selling at end_price (0 = sell at the floor)
+    // PDA namespace
+    pub namespace: u8, // Index into ESCROW_PREFIXES of the prefix the escrow's address is derived from
+    // Protocol fee recipient
+    pub fee_recipient: [u8; 32], // Wallet whose token B account collects the fee (zero = no fee)
+    // Partial escrow fill mode
+    pub all_or_nothing: u8, // Takes must fill all the remaining token A (0 = any amount)
+    // Oracle escrow price feed account
+    pub oracle_feed: [u8; 32], // The only feed takes may price against; owned by oracle_program
 }
 
 impl DataLen for Escrow {
     const LEN: usize = core::mem::size_of::<Self>();
 }
 
+/// Size in bytes of an escrow account, for clients pre-funding its rent.
+/// This is part of the account layout and only changes alongside it.
+pub const ESCROW_ACCOUNT_SIZE: usize = 960;
+
+const _: () = assert!(ESCROW_ACCOUNT_SIZE == Escrow::LEN);
+
+/// Upper bound on the escrow account size. Growing past it should be a deliberate
+/// decision, since every byte adds to the rent each maker locks up.
+pub const MAX_ESCROW_ACCOUNT_SIZE: usize = 1024;
+
+const _: () = assert!(Escrow::LEN <= MAX_ESCROW_ACCOUNT_SIZE);
+
+/// PDA prefixes escrows may be created under, indexed by `Escrow::namespace`. Protocols
+/// running several flows for the same makers (OTC offers and auctions, say) can keep each in
+/// its own namespace instead of partitioning seeds.
+pub const ESCROW_PREFIXES: [&str; 2] = [Escrow::PREFIX, "Auction"];
+
 impl Escrow {
     pub const PREFIX: &'static str = "Escrow";
 
+    /// Default namespace, deriving addresses from `Escrow::PREFIX`
+    pub const NAMESPACE_ESCROW: u8 = 0;
+    /// Namespace deriving addresses from `"Auction"`
+    pub const NAMESPACE_AUCTION: u8 = 1;
+
+    /// Open for takes and maker changes
+    pub const STATE_ACTIVE: u8 = 0;
+    /// Fully taken; only closing the escrow is left
+    pub const STATE_COMPLETED: u8 = 1;
+    /// Cancelled by the maker
+    pub const STATE_CANCELLED: u8 = 2;
+
+    /// PDA prefix of `namespace`, rejecting namespaces outside `ESCROW_PREFIXES`
+    pub fn namespace_prefix(namespace: u8) -> Result<&'static [u8], ProgramError> {
+        ESCROW_PREFIXES
+            .get(namespace as usize)
+            .map(|prefix| prefix.as_bytes())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    /// PDA prefix this escrow's address is derived from
+    pub fn prefix(&self) -> &'static [u8] {
+        Self::namespace_prefix(self.namespace).unwrap_or(Self::PREFIX.as_bytes())
+    }
+
     pub fn validate_escrow_pda(
         pda: &Pubkey,
+        prefix: &[u8],
         owner: &Pubkey,
         bump: &u8,
-        seed: &[u8; 2],
+        seed: &[u8; ESCROW_SEED_LEN],
     ) -> Result<(), ProgramError> {
-        let seed_with_bump = &[Self::PREFIX.as_bytes(), owner, seed, &[*bump]];
+        let seed_with_bump = &[prefix, owner, seed, &[*bump]];
         let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
         msg!("Derived: {:?}", derived);
         if derived != *pda {
@@ -68,10 +239,66 @@ impl Escrow {
         Ok(())
     }
 
+    /// Check that `pda` is the escrow address for `owner`'s `seed` and `bump` under `prefix`
+    /// by hashing the seeds directly. Unlike `validate_escrow_pda` this skips the off-curve
+    /// check, which only matters when deriving an address, and costs a `sol_sha256` (~150 CUs)
+    /// instead of a `create_program_address` (1,500 CUs).
+    pub fn verify_escrow_address(
+        pda: &Pubkey,
+        prefix: &[u8],
+        owner: &Pubkey,
+        bump: &u8,
+        seed: &[u8; ESCROW_SEED_LEN],
+    ) -> Result<(), ProgramError> {
+        #[cfg(target_os = "solana")]
+        {
+            const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+            if crate::states::hashv(&[prefix, owner, seed, &[*bump], &crate::ID, PDA_MARKER])
+                != *pda
+            {
+                return Err(EscrowErrorCode::PdaMismatch.into());
+            }
+            debug_assert!(Self::validate_escrow_pda(pda, prefix, owner, bump, seed).is_ok());
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "solana"))]
+        {
+            Self::validate_escrow_pda(pda, prefix, owner, bump, seed)
+        }
+    }
+
+    /// Stable short id for the maker's escrow at `seed`, logged with every escrow event so
+    /// indexers can key escrows by it. FNV-1a over `maker || seed`, so clients compute the
+    /// same value off-chain without any syscalls.
+    pub fn escrow_id(maker: &Pubkey, seed: &[u8; ESCROW_SEED_LEN]) -> u64 {
+        maker
+            .iter()
+            .chain(seed)
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    /// Whether `bump` is the canonical bump for the maker's escrow at `seed` under `prefix`:
+    /// it must derive an off-curve address and no higher bump may do so
+    pub fn is_canonical_bump(
+        prefix: &[u8],
+        maker: &Pubkey,
+        seed: &[u8; ESCROW_SEED_LEN],
+        bump: u8,
+    ) -> bool {
+        let derives = |bump: u8| {
+            pubkey::create_program_address(&[prefix, maker, seed, &[bump]], &crate::ID).is_ok()
+        };
+        // 255 has no higher bump, and `bump + 1` would overflow for it
+        derives(bump) && (bump == u8::MAX || !(bump + 1..=u8::MAX).any(derives))
+    }
+
     pub fn new(
         escrow_type: EscrowType,
         maker_pubkey: [u8; 32],
-        seed: [u8; 2],
+        seed: [u8; ESCROW_SEED_LEN],
         token_a_mint: [u8; 32],
         token_a_amount: u64,
         token_b_mint: [u8; 32],
@@ -92,29 +319,83 @@ impl Escrow {
             start_time: 0,
             duration: 0,
             end_time: 0,
+            fill_cooldown: 0,
+            last_fill_time: 0,
+            original_token_a_amount: token_a_amount,
+            fill_count: 0,
+            fill_history: [FillRecord::default(); FILL_HISTORY_LEN],
+            token_b_vault: [0; 32],
+            discount_threshold: 0,
+            volume_discount_bps: 0,
+            price_mode: MakeEscrowIx::PRICE_MODE_TOTAL,
+            token_a_decimals: 0,
+            token_b_decimals: 0,
+            refund_account: [0; 32],
+            fills_enabled: 1,
+            proceeds_floor: 0,
+            proceeds_deadline: 0,
+            token_b_collected: 0,
+            oracle_program: [0; 32],
+            oracle_max_age: 0,
+            expiry: 0,
+            highest_bid: 0,
+            highest_bidder: [0; 32],
+            auction_end: 0,
+            fee_bps: 0,
+            state: Self::STATE_ACTIVE,
+            allowed_taker: [0; 32],
+            vault: [0; 32],
+            token_program: pinocchio_token::ID,
+            expire_at_floor: 0,
+            namespace: Self::NAMESPACE_ESCROW,
+            fee_recipient: [0; 32],
+            all_or_nothing: 0,
+            oracle_feed: [0; 32],
         }
     }
 
     pub fn initialize(
         escrow_acc: &AccountInfo,
         ix_data: &MakeEscrowIx,
-        seed: [u8; 2],
-        token_a_mint: [u8; 32],
-        token_b_mint: [u8; 32],
-        maker_pubkey: [u8; 32],
-        start_time: u64,
-        end_time: u64,
+        init: &EscrowInit,
     ) -> ProgramResult {
         let escrow = unsafe { try_from_account_info_mut::<Escrow>(escrow_acc) }?;
+        let EscrowInit {
+            maker_pubkey,
+            token_a_mint,
+            token_b_mint,
+            start_time,
+            end_time,
+            token_a_decimals,
+            token_b_decimals,
+            vault,
+            token_program,
+        } = *init;
 
         escrow.maker_pubkey = maker_pubkey;
-        escrow.seed = seed;
+        escrow.seed = ix_data.seed;
         escrow.escrow_type = ix_data.escrow_type;
         escrow.token_a_mint = token_a_mint;
         escrow.token_a_amount = ix_data.token_a_amount;
+        escrow.original_token_a_amount = ix_data.token_a_amount;
         escrow.token_b_mint = token_b_mint;
         escrow.token_b_amount = ix_data.token_b_amount;
         escrow.bump = ix_data.bump;
+        escrow.token_b_vault = ix_data.token_b_vault;
+        escrow.refund_account = ix_data.refund_account;
+        escrow.price_mode = ix_data.price_mode;
+        escrow.token_a_decimals = token_a_decimals;
+        escrow.token_b_decimals = token_b_decimals;
+        escrow.fills_enabled = 1;
+        escrow.expiry = ix_data.expiry;
+        escrow.fee_bps = ix_data.fee_bps;
+        escrow.state = Self::STATE_ACTIVE;
+        escrow.allowed_taker = ix_data.allowed_taker;
+        escrow.vault = vault;
+        escrow.token_program = token_program;
+        escrow.namespace = ix_data.namespace;
+        escrow.fee_recipient = ix_data.fee_recipient;
+        escrow.all_or_nothing = ix_data.all_or_nothing as u8;
 
         // Initialize Dutch auction fields if needed
         if ix_data.escrow_type == EscrowType::DutchAuction {
@@ -123,6 +404,29 @@ impl Escrow {
             escrow.duration = ix_data.duration;
             escrow.start_time = start_time;
             escrow.end_time = end_time;
+            escrow.expire_at_floor = ix_data.expire_at_floor as u8;
+        }
+
+        // Initialize partial fill fields if needed
+        if ix_data.escrow_type == EscrowType::Partial {
+            escrow.fill_cooldown = ix_data.fill_cooldown;
+            escrow.discount_threshold = ix_data.discount_threshold;
+            escrow.volume_discount_bps = ix_data.volume_discount_bps;
+            escrow.proceeds_floor = ix_data.proceeds_floor;
+            escrow.proceeds_deadline = ix_data.proceeds_deadline;
+        }
+
+        if ix_data.escrow_type == EscrowType::Oracle {
+            escrow.oracle_program = ix_data.oracle_program;
+            escrow.oracle_max_age = ix_data.oracle_max_age;
+            escrow.oracle_feed = ix_data.oracle_feed;
+        }
+
+        // English auctions take bids from start_time until auction_end
+        if ix_data.escrow_type == EscrowType::English {
+            escrow.duration = ix_data.duration;
+            escrow.start_time = start_time;
+            escrow.auction_end = end_time;
         }
 
         Ok(())
@@ -145,16 +449,17 @@ impl Escrow {
                 let time_elapsed = current_time - self.start_time;
                 let total_duration = self.end_time - self.start_time;
 
-                // Calculate price drop using safe arithmetic
-                let price_drop = self.start_price - self.end_price;
+                // Calculate price drop using safe arithmetic; make rejects floors above the
+                // start price, so a corrupted escrow simply doesn't decay
+                let price_drop = self.start_price.saturating_sub(self.end_price);
 
                 // Use multiplication before division to maintain precision
                 // Formula: current_price = start_price - (price_drop * time_elapsed / total_duration)
                 let price_reduction =
                     (price_drop as u128 * time_elapsed as u128) / total_duration as u128;
 
-                // Convert back to u64 safely
-                self.start_price - (price_reduction as u64)
+                // The reduction never exceeds price_drop, but never underflow regardless
+                self.start_price.saturating_sub(price_reduction as u64)
             }
             _ => self.token_b_amount, // For non-Dutch auctions, return the fixed amount
         }
@@ -186,53 +491,746 @@ impl Escrow {
         }
     }
 
-    // pub fn pack(&self) -> [u8; Self::LEN] {
-    //     let mut data = [0u8; Self::LEN];
-    //     data[0..32].copy_from_slice(&self.maker);
-    //     data[32..34].copy_from_slice(&self.seed);
-    //     data[34] = self.escrow_type as u8;
-    //     data[35..67].copy_from_slice(&self.token_giver_mint);
-    //     data[67..99].copy_from_slice(&self.token_take_mint);
-    //     data[99..131].copy_from_slice(&self.token_take_amount.to_le_bytes());
-    //     data[131] = self.bump;
-    //     data
-    // }
-
-    // pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
-    //     let maker = data[0..32]
-    //         .try_into()
-    //         .map_err(|_| ProgramError::InvalidInstructionData)?;
-    //     let seed = data[32..34]
-    //         .try_into()
-    //         .map_err(|_| ProgramError::InvalidInstructionData)?;
-    //     let escrow_type =
-    //         EscrowType::try_from(data[34]).map_err(|_| ProgramError::InvalidInstructionData)?;
-    //     let token_giver_mint = data[35..67]
-    //         .try_into()
-    //         .map_err(|_| ProgramError::InvalidInstructionData)?;
-    //     let token_giver_amount = u64::from_le_bytes(
-    //         data[67..99]
-    //             .try_into()
-    //             .map_err(|_| ProgramError::InvalidInstructionData)?,
-    //     );
-    //     let token_take_mint = data[99..131]
-    //         .try_into()
-    //         .map_err(|_| ProgramError::InvalidInstructionData)?;
-    //     let token_take_amount = u64::from_le_bytes(
-    //         data[131..163]
-    //             .try_into()
-    //             .map_err(|_| ProgramError::InvalidInstructionData)?,
-    //     );
-    //     let bump = data[163];
-    //     Ok(Self {
-    //         maker,
-    //         seed,
-    //         escrow_type,
-    //         token_giver_mint,
-    //         token_giver_amount,
-    //         token_take_mint,
-    //         token_take_amount,
-    //         bump,
-    //     })
-    // }
+    /// Wallet that made the escrow and receives its token B
+    pub fn maker(&self) -> Pubkey {
+        self.maker_pubkey
+    }
+
+    /// Whether the escrow is a Dutch auction
+    pub fn is_dutch(&self) -> bool {
+        self.escrow_type == EscrowType::DutchAuction
+    }
+
+    /// Token A still on offer
+    pub fn remaining(&self) -> u64 {
+        self.token_a_amount
+    }
+
+    /// Token B required at `now`, as `get_required_token_b_amount`
+    pub fn current_price(&self, now: u64) -> u64 {
+        self.get_required_token_b_amount(now)
+    }
+
+    /// Current Dutch auction price, rejected if a misconfigured escrow would
+    /// charge more than the advertised start price
+    pub fn checked_dutch_price(&self, current_time: u64) -> Result<u64, ProgramError> {
+        let price = self.calculate_dutch_price(current_time);
+        if price > self.start_price {
+            return Err(EscrowErrorCode::PriceOutOfBounds.into());
+        }
+        Ok(price)
+    }
+
+    /// Token B owed for a Dutch auction fill of `token_a_amount` at `current_time`.
+    /// The decayed price applies to the original lot, so each unit costs
+    /// `price / original_token_a_amount`, rounded up in the maker's favor.
+    pub fn dutch_fill_cost(
+        &self,
+        token_a_amount: u64,
+        current_time: u64,
+    ) -> Result<u64, ProgramError> {
+        let lot_price = self.checked_dutch_price(current_time)? as u128;
+        let original = self.original_token_a_amount as u128;
+        u64::try_from((lot_price * token_a_amount as u128).div_ceil(original))
+            .map_err(|_| EscrowErrorCode::ArithmeticOverflow.into())
+    }
+
+    /// Share of the remaining token B ask that a Partial fill of `token_a_amount` covers,
+    /// rounded up in the maker's favor. A fill of everything left covers the whole ask, so
+    /// sequential fills add up to exactly the original ask.
+    pub fn partial_fill_token_b(&self, token_a_amount: u64) -> Result<u64, ProgramError> {
+        let token_b_amount = (self.token_b_amount as u128 * token_a_amount as u128)
+            .div_ceil(self.token_a_amount as u128);
+        u64::try_from(token_b_amount).map_err(|_| EscrowErrorCode::ArithmeticOverflow.into())
+    }
+
+    /// Token B a taker would pay to fill `token_a_amount` at `current_time`, or `None`
+    /// if this escrow can't fill exactly that amount right now
+    pub fn quote_fill(
+        &self,
+        token_a_amount: u64,
+        current_time: u64,
+    ) -> Result<Option<u64>, ProgramError> {
+        if token_a_amount == 0
+            || token_a_amount > self.token_a_amount
+            || self.is_past_expiry(current_time)
+            || self.is_past_floor_deadline(current_time)
+            || (self.all_or_nothing != 0 && token_a_amount != self.token_a_amount)
+        {
+            return Ok(None);
+        }
+        match self.escrow_type {
+            EscrowType::Simple => {
+                Ok((token_a_amount == self.token_a_amount).then_some(self.token_b_amount))
+            }
+            EscrowType::Partial => {
+                if self.fills_enabled == 0
+                    || current_time < self.last_fill_time.saturating_add(self.fill_cooldown)
+                {
+                    return Ok(None);
+                }
+                let token_b_owed =
+                    self.token_b_base_units(self.partial_fill_token_b(token_a_amount)?)?;
+                Ok(Some(
+                    self.apply_volume_discount(token_a_amount, token_b_owed),
+                ))
+            }
+            EscrowType::DutchAuction => {
+                self.dutch_fill_cost(token_a_amount, current_time).map(Some)
+            }
+            EscrowType::Oracle | EscrowType::English => Ok(None),
+        }
+    }
+
+    /// Convert a token B amount in this escrow's price units into token B base units.
+    /// Decimals-normalized escrows quote token B at token A's decimals; scaling down
+    /// rounds up in the maker's favor.
+    pub fn token_b_base_units(&self, amount: u64) -> Result<u64, ProgramError> {
+        if self.price_mode != MakeEscrowIx::PRICE_MODE_DECIMALS_NORMALIZED {
+            return Ok(amount);
+        }
+        let scale = |exponent: u8| {
+            10u64
+                .checked_pow(exponent as u32)
+                .ok_or(EscrowErrorCode::ArithmeticOverflow)
+        };
+        if self.token_b_decimals >= self.token_a_decimals {
+            amount
+                .checked_mul(scale(self.token_b_decimals - self.token_a_decimals)?)
+                .ok_or(EscrowErrorCode::ArithmeticOverflow.into())
+        } else {
+            Ok(amount.div_ceil(scale(self.token_a_decimals - self.token_b_decimals)?))
+        }
+    }
+
+    /// Apply the volume discount to the token B charged for a partial take of `token_a_amount`
+    pub fn apply_volume_discount(&self, token_a_amount: u64, token_b_amount: u64) -> u64 {
+        if self.discount_threshold == 0 || token_a_amount <= self.discount_threshold {
+            return token_b_amount;
+        }
+        let discount = token_b_amount as u128 * self.volume_discount_bps as u128 / 10_000;
+        token_b_amount.saturating_sub(discount as u64)
+    }
+
+    /// Whether the proceeds floor still locks token A in the escrow: takers haven't
+    /// paid the floor yet and its deadline hasn't passed
+    pub fn is_floor_locked(&self, current_time: u64) -> bool {
+        self.proceeds_floor != 0
+            && self.token_b_collected < self.proceeds_floor
+            && current_time < self.proceeds_deadline
+    }
+
+    /// Whether takers pay this escrow in lamports rather than an SPL token B
+    pub fn is_priced_in_sol(&self) -> bool {
+        self.token_b_mint == SOL_TOKEN_B_MINT
+    }
+
+    /// Price of one whole token A in whole token B, scaled by `NORMALIZED_PRICE_SCALE`, using
+    /// the mint decimals captured at make. This is the remaining ask over the remaining token A,
+    /// so the start price for Dutch auctions and the reserve for English ones. `None` once the
+    /// escrow is empty or if the price doesn't fit in a `u64`.
+    pub fn normalized_price(&self) -> Option<u64> {
+        if self.token_a_amount == 0 {
+            return None;
+        }
+        let token_b_amount = self.token_b_base_units(self.token_b_amount).ok()? as u128;
+        let scale = |exponent: u8| 10u128.checked_pow(exponent as u32);
+        let numerator = token_b_amount
+            .checked_mul(scale(self.token_a_decimals)?)?
+            .checked_mul(NORMALIZED_PRICE_SCALE as u128)?;
+        let denominator =
+            (self.token_a_amount as u128).checked_mul(scale(self.token_b_decimals)?)?;
+        u64::try_from(numerator / denominator).ok()
+    }
+
+    /// Whether the maker's expiry has passed, after which takes are rejected
+    pub fn is_past_expiry(&self, current_time: u64) -> bool {
+        self.expiry != 0 && current_time > self.expiry
+    }
+
+    /// Whether the escrow can no longer be filled on its original terms, with token A
+    /// left over: its expiry has passed, it is a Dutch auction that stopped selling at
+    /// `end_time`, or it is an English auction whose bidding closed. Other Dutch auctions
+    /// keep selling at the floor after `end_time`.
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        self.token_a_amount > 0
+            && (self.is_past_expiry(current_time)
+                || self.is_past_floor_deadline(current_time)
+                || (self.escrow_type == EscrowType::English && current_time >= self.auction_end))
+    }
+
+    /// Whether a Dutch auction whose maker opted out of selling at the floor has reached
+    /// `end_time`, after which takes are rejected
+    pub fn is_past_floor_deadline(&self, current_time: u64) -> bool {
+        self.escrow_type == EscrowType::DutchAuction
+            && self.expire_at_floor != 0
+            && current_time >= self.end_time
+    }
+
+    /// Whether an English auction holds a bidder's token B, which only settling may release
+    pub fn has_bids(&self) -> bool {
+        self.highest_bid != 0
+    }
+
+    /// Whether `taker` may take this escrow: anyone for a public escrow, otherwise only the
+    /// counterparty the maker named
+    pub fn allows_taker(&self, taker: &Pubkey) -> bool {
+        self.allowed_taker == [0; 32] || &self.allowed_taker == taker
+    }
+
+    /// Whether `taker` is the maker of a Simple or Dutch auction escrow. Such a take only moves
+    /// the maker's own tokens (and can game a Dutch auction's timing); Partial escrows still
+    /// allow makers to fill themselves.
+    pub fn is_self_take(&self, taker: &Pubkey) -> bool {
+        taker == &self.maker_pubkey
+            && matches!(
+                self.escrow_type,
+                EscrowType::Simple | EscrowType::DutchAuction
+            )
+    }
+
+    /// Move an active escrow to `state`, rejecting one a take or cancel already finished.
+    /// Takes and cancels call this before their transfers, so a re-entrant call in the same
+    /// instruction finds the escrow no longer active.
+    pub fn transition(&mut self, state: u8) -> ProgramResult {
+        if self.state != Self::STATE_ACTIVE {
+            return Err(EscrowErrorCode::EscrowNotActive.into());
+        }
+        self.state = state;
+        Ok(())
+    }
+
+    /// Get the lifecycle status of this escrow at `current_time`
+    pub fn status(&self, current_time: u64) -> EscrowStatus {
+        if self.token_a_amount == 0 {
+            EscrowStatus::Closed
+        } else if self.is_expired(current_time) {
+            EscrowStatus::Expired
+        } else if self.token_a_amount < self.original_token_a_amount {
+            EscrowStatus::PartiallyFilled
+        } else {
+            EscrowStatus::Open
+        }
+    }
+
+    /// Record a partial fill, overwriting the oldest entry once the history is full
+    pub fn record_fill(&mut self, taker: [u8; 32], token_a_amount: u64, timestamp: u64) {
+        let index = (self.fill_count % FILL_HISTORY_LEN as u64) as usize;
+        self.fill_history[index] = FillRecord {
+            taker,
+            token_a_amount,
+            timestamp,
+        };
+        self.fill_count += 1;
+    }
+
+    /// Iterate over the recorded fills, most recent first
+    pub fn recent_fills(&self) -> impl Iterator<Item = &FillRecord> {
+        let recorded = self.fill_count.min(FILL_HISTORY_LEN as u64) as usize;
+        (0..recorded).map(move |i| {
+            let index = (self.fill_count as usize - 1 - i) % FILL_HISTORY_LEN;
+            &self.fill_history[index]
+        })
+    }
+
+    /// Serialize into the escrow account layout, so `unpack` reads back account data as-is
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        let mut put = |offset: usize, bytes: &[u8]| {
+            data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        put(offset_of!(Self, maker_pubkey), &self.maker_pubkey);
+        put(offset_of!(Self, seed), &self.seed);
+        put(offset_of!(Self, escrow_type), &[self.escrow_type as u8]);
+        put(offset_of!(Self, token_a_mint), &self.token_a_mint);
+        put(
+            offset_of!(Self, token_a_amount),
+            &self.token_a_amount.to_le_bytes(),
+        );
+        put(offset_of!(Self, token_b_mint), &self.token_b_mint);
+        put(
+            offset_of!(Self, token_b_amount),
+            &self.token_b_amount.to_le_bytes(),
+        );
+        put(offset_of!(Self, bump), &[self.bump]);
+        put(
+            offset_of!(Self, start_price),
+            &self.start_price.to_le_bytes(),
+        );
+        put(offset_of!(Self, end_price), &self.end_price.to_le_bytes());
+        put(offset_of!(Self, start_time), &self.start_time.to_le_bytes());
+        put(offset_of!(Self, duration), &self.duration.to_le_bytes());
+        put(offset_of!(Self, end_time), &self.end_time.to_le_bytes());
+        put(
+            offset_of!(Self, fill_cooldown),
+            &self.fill_cooldown.to_le_bytes(),
+        );
+        put(
+            offset_of!(Self, last_fill_time),
+            &self.last_fill_time.to_le_bytes(),
+        );
+        put(
+            offset_of!(Self, original_token_a_amount),
+            &self.original_token_a_amount.to_le_bytes(),
+        );
+        put(offset_of!(Self, fill_count), &self.fill_count.to_le_bytes());
+        for (i, record) in self.fill_history.iter().enumerate() {
+            put(
+                offset_of!(Self, fill_history) + i * FillRecord::LEN,
+                &record.pack(),
+            );
+        }
+        put(offset_of!(Self, token_b_vault), &self.token_b_vault);
+        put(
+            offset_of!(Self, discount_threshold),
+            &self.discount_threshold.to_le_bytes(),
+        );
+        put(
+            offset_of!(Self, volume_discount_bps),
+            &self.volume_discount_bps.to_le_bytes(),
+        );
+        put(offset_of!(Self, price_mode), &[self.price_mode]);
+        put(offset_of!(Self, token_a_decimals), &[self.token_a_decimals]);
+        put(offset_of!(Self, token_b_decimals), &[self.token_b_decimals]);
+        put(offset_of!(Self, refund_account), &self.refund_account);
+        put(offset_of!(Self, fills_enabled), &[self.fills_enabled]);
+        put(
+            offset_of!(Self, proceeds_floor),
+            &self.proceeds_floor.to_le_bytes(),
+        );
+        put(
+            offset_of!(Self, proceeds_deadline),
+            &self.proceeds_deadline.to_le_bytes(),
+        );
+        put(
+            offset_of!(Self, token_b_collected),
+            &self.token_b_collected.to_le_bytes(),
+        );
+        put(offset_of!(Self, oracle_program), &self.oracle_program);
+        put(
+            offset_of!(Self, oracle_max_age),
+            &self.oracle_max_age.to_le_bytes(),
+        );
+        put(offset_of!(Self, expiry), &self.expiry.to_le_bytes());
+        put(
+            offset_of!(Self, highest_bid),
+            &self.highest_bid.to_le_bytes(),
+        );
+        put(offset_of!(Self, highest_bidder), &self.highest_bidder);
+        put(
+            offset_of!(Self, auction_end),
+            &self.auction_end.to_le_bytes(),
+        );
+        put(offset_of!(Self, fee_bps), &self.fee_bps.to_le_bytes());
+        put(offset_of!(Self, state), &[self.state]);
+        put(offset_of!(Self, allowed_taker), &self.allowed_taker);
+        put(offset_of!(Self, vault), &self.vault);
+        put(offset_of!(Self, token_program), &self.token_program);
+        put(offset_of!(Self, expire_at_floor), &[self.expire_at_floor]);
+        put(offset_of!(Self, namespace), &[self.namespace]);
+        put(offset_of!(Self, fee_recipient), &self.fee_recipient);
+        put(offset_of!(Self, all_or_nothing), &[self.all_or_nothing]);
+        put(offset_of!(Self, oracle_feed), &self.oracle_feed);
+        data
+    }
+
+    /// Deserialize escrow account data, e.g. as fetched by an off-chain client
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let byte = |offset: usize| data[offset];
+        let key = |offset: usize| -> [u8; 32] { data[offset..offset + 32].try_into().unwrap() };
+        let u16_at = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        let mut fill_history = [FillRecord::default(); FILL_HISTORY_LEN];
+        for (i, record) in fill_history.iter_mut().enumerate() {
+            let offset = offset_of!(Self, fill_history) + i * FillRecord::LEN;
+            *record = FillRecord {
+                taker: key(offset),
+                token_a_amount: u64_at(offset + 32),
+                timestamp: u64_at(offset + 40),
+            };
+        }
+
+        let seed_offset = offset_of!(Self, seed);
+        Ok(Self {
+            maker_pubkey: key(offset_of!(Self, maker_pubkey)),
+            seed: data[seed_offset..seed_offset + ESCROW_SEED_LEN]
+                .try_into()
+                .unwrap(),
+            escrow_type: EscrowType::try_from(byte(offset_of!(Self, escrow_type)))
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+            token_a_mint: key(offset_of!(Self, token_a_mint)),
+            token_a_amount: u64_at(offset_of!(Self, token_a_amount)),
+            token_b_mint: key(offset_of!(Self, token_b_mint)),
+            token_b_amount: u64_at(offset_of!(Self, token_b_amount)),
+            bump: byte(offset_of!(Self, bump)),
+            start_price: u64_at(offset_of!(Self, start_price)),
+            end_price: u64_at(offset_of!(Self, end_price)),
+            start_time: u64_at(offset_of!(Self, start_time)),
+            duration: u64_at(offset_of!(Self, duration)),
+            end_time: u64_at(offset_of!(Self, end_time)),
+            fill_cooldown: u64_at(offset_of!(Self, fill_cooldown)),
+            last_fill_time: u64_at(offset_of!(Self, last_fill_time)),
+            original_token_a_amount: u64_at(offset_of!(Self, original_token_a_amount)),
+            fill_count: u64_at(offset_of!(Self, fill_count)),
+            fill_history,
+            token_b_vault: key(offset_of!(Self, token_b_vault)),
+            discount_threshold: u64_at(offset_of!(Self, discount_threshold)),
+            volume_discount_bps: u16_at(offset_of!(Self, volume_discount_bps)),
+            price_mode: byte(offset_of!(Self, price_mode)),
+            token_a_decimals: byte(offset_of!(Self, token_a_decimals)),
+            token_b_decimals: byte(offset_of!(Self, token_b_decimals)),
+            refund_account: key(offset_of!(Self, refund_account)),
+            fills_enabled: byte(offset_of!(Self, fills_enabled)),
+            proceeds_floor: u64_at(offset_of!(Self, proceeds_floor)),
+            proceeds_deadline: u64_at(offset_of!(Self, proceeds_deadline)),
+            token_b_collected: u64_at(offset_of!(Self, token_b_collected)),
+            oracle_program: key(offset_of!(Self, oracle_program)),
+            oracle_max_age: u64_at(offset_of!(Self, oracle_max_age)),
+            expiry: u64_at(offset_of!(Self, expiry)),
+            highest_bid: u64_at(offset_of!(Self, highest_bid)),
+            highest_bidder: key(offset_of!(Self, highest_bidder)),
+            auction_end: u64_at(offset_of!(Self, auction_end)),
+            fee_bps: u16_at(offset_of!(Self, fee_bps)),
+            state: byte(offset_of!(Self, state)),
+            allowed_taker: key(offset_of!(Self, allowed_taker)),
+            vault: key(offset_of!(Self, vault)),
+            token_program: key(offset_of!(Self, token_program)),
+            expire_at_floor: byte(offset_of!(Self, expire_at_floor)),
+            namespace: byte(offset_of!(Self, namespace)),
+            fee_recipient: key(offset_of!(Self, fee_recipient)),
+            all_or_nothing: byte(offset_of!(Self, all_or_nothing)),
+            oracle_feed: key(offset_of!(Self, oracle_feed)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrow_type_round_trips_through_u8() {
+        for escrow_type in [
+            EscrowType::Simple,
+            EscrowType::Partial,
+            EscrowType::DutchAuction,
+            EscrowType::Oracle,
+            EscrowType::English,
+        ] {
+            assert_eq!(EscrowType::try_from(escrow_type as u8), Ok(escrow_type));
+        }
+    }
+
+    #[test]
+    fn escrow_type_rejects_unknown_bytes() {
+        for byte in 5..=u8::MAX {
+            assert_eq!(
+                EscrowType::try_from(byte),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn escrow_round_trips_through_bytes() {
+        let mut escrow = Escrow::new(
+            EscrowType::DutchAuction,
+            [1; 32],
+            42u64.to_le_bytes(),
+            [2; 32],
+            5000,
+            [3; 32],
+            10000,
+            254,
+        );
+        escrow.start_price = 10000;
+        escrow.end_price = 2500;
+        escrow.start_time = 1_700_000_000;
+        escrow.duration = 3600;
+        escrow.end_time = 1_700_003_600;
+        escrow.fill_cooldown = 30;
+        escrow.token_a_amount = 4000;
+        escrow.record_fill([4; 32], 1000, 1_700_000_100);
+        escrow.last_fill_time = 1_700_000_100;
+        escrow.token_b_vault = [5; 32];
+        escrow.discount_threshold = 700;
+        escrow.volume_discount_bps = 250;
+        escrow.price_mode = MakeEscrowIx::PRICE_MODE_DECIMALS_NORMALIZED;
+        escrow.token_a_decimals = 6;
+        escrow.token_b_decimals = 9;
+        escrow.refund_account = [6; 32];
+        escrow.fills_enabled = 0;
+        escrow.proceeds_floor = 800;
+        escrow.proceeds_deadline = 1_700_086_400;
+        escrow.token_b_collected = 2500;
+        escrow.oracle_program = [7; 32];
+        escrow.oracle_max_age = 60;
+        escrow.expiry = 1_800_000_000;
+        escrow.highest_bid = 3000;
+        escrow.highest_bidder = [8; 32];
+        escrow.auction_end = 1_700_007_200;
+        escrow.fee_bps = 30;
+        escrow.state = Escrow::STATE_COMPLETED;
+        escrow.allowed_taker = [9; 32];
+        escrow.vault = [10; 32];
+        escrow.token_program = [11; 32];
+        escrow.expire_at_floor = 1;
+        escrow.namespace = Escrow::NAMESPACE_AUCTION;
+        escrow.fee_recipient = [12; 32];
+        escrow.all_or_nothing = 1;
+        escrow.oracle_feed = [13; 32];
+
+        let data = escrow.pack();
+        assert_eq!(Escrow::unpack(&data), Ok(escrow));
+        assert_eq!(&data[32..40], &42u64.to_le_bytes());
+
+        assert!(Escrow::unpack(&data[..Escrow::LEN - 1]).is_err());
+        let mut bad_type = data;
+        bad_type[offset_of!(Escrow, escrow_type)] = 9;
+        assert!(Escrow::unpack(&bad_type).is_err());
+    }
+
+    #[test]
+    fn volume_discount_applies_only_above_threshold() {
+        let mut escrow = Escrow::new(
+            EscrowType::Partial,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            5000,
+            [3; 32],
+            10000,
+            255,
+        );
+        assert_eq!(escrow.apply_volume_discount(2000, 4000), 4000);
+
+        escrow.discount_threshold = 1000;
+        escrow.volume_discount_bps = 500;
+        assert_eq!(escrow.apply_volume_discount(1000, 2000), 2000);
+        assert_eq!(escrow.apply_volume_discount(2000, 4000), 3800);
+    }
+
+    #[test]
+    fn dutch_price_above_start_price_is_rejected() {
+        let mut escrow = Escrow::new(
+            EscrowType::DutchAuction,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            0,
+            255,
+        );
+        escrow.start_time = 100;
+        escrow.end_time = 200;
+        escrow.start_price = 5000;
+        escrow.end_price = 1000;
+        assert_eq!(escrow.dutch_fill_cost(500, 200), Ok(500));
+
+        // A corrupted escrow whose floor sits above its start price
+        escrow.end_price = 6000;
+        assert_eq!(escrow.calculate_dutch_price(150), 5000);
+        assert_eq!(
+            escrow.dutch_fill_cost(500, 200),
+            Err(EscrowErrorCode::PriceOutOfBounds.into())
+        );
+    }
+
+    #[test]
+    fn dutch_fills_pay_their_share_of_the_lot_price() {
+        let mut escrow = Escrow::new(
+            EscrowType::DutchAuction,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            0,
+            255,
+        );
+        escrow.start_time = 100;
+        escrow.end_time = 200;
+        escrow.start_price = 10000;
+        escrow.end_price = 5000;
+        assert_eq!(escrow.dutch_fill_cost(500, 100), Ok(5000));
+
+        // What remains is still priced against the original lot as it decays
+        escrow.token_a_amount = 500;
+        assert_eq!(escrow.dutch_fill_cost(500, 150), Ok(3750));
+
+        // Lots and prices whose product overflows u64
+        escrow.original_token_a_amount = u64::MAX;
+        escrow.start_price = u64::MAX;
+        escrow.end_price = u64::MAX;
+        assert_eq!(escrow.dutch_fill_cost(u64::MAX / 2, 150), Ok(u64::MAX / 2));
+    }
+
+    #[test]
+    fn normalized_price_accounts_for_mint_decimals() {
+        let mut escrow = Escrow::new(
+            EscrowType::Simple,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            2_000_000,
+            [3; 32],
+            3_000_000_000,
+            255,
+        );
+        // 2 token A at 6 decimals for 3 token B at 9 decimals
+        escrow.token_a_decimals = 6;
+        escrow.token_b_decimals = 9;
+        assert_eq!(escrow.normalized_price(), Some(1_500_000_000));
+
+        // The same ask quoted at token A's decimals
+        escrow.price_mode = MakeEscrowIx::PRICE_MODE_DECIMALS_NORMALIZED;
+        escrow.token_b_amount = 3_000_000;
+        assert_eq!(escrow.normalized_price(), Some(1_500_000_000));
+
+        escrow.token_a_amount = 0;
+        assert_eq!(escrow.normalized_price(), None);
+    }
+
+    #[test]
+    fn expiry_ends_takes_and_reports_expired() {
+        let mut escrow = Escrow::new(
+            EscrowType::Simple,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            2000,
+            255,
+        );
+        assert_eq!(escrow.status(u64::MAX), EscrowStatus::Open);
+
+        escrow.expiry = 500;
+        assert_eq!(escrow.status(500), EscrowStatus::Open);
+        assert_eq!(escrow.quote_fill(1000, 500), Ok(Some(2000)));
+        assert_eq!(escrow.status(501), EscrowStatus::Expired);
+        assert_eq!(escrow.quote_fill(1000, 501), Ok(None));
+    }
+
+    #[test]
+    fn accessors_read_escrow_fields() {
+        let mut escrow = Escrow::new(
+            EscrowType::Simple,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            2000,
+            255,
+        );
+        assert_eq!(escrow.maker(), [1; 32]);
+        assert!(!escrow.is_dutch());
+        assert_eq!(escrow.remaining(), 1000);
+        assert_eq!(escrow.current_price(u64::MAX), 2000);
+
+        escrow.escrow_type = EscrowType::DutchAuction;
+        escrow.start_price = 10000;
+        escrow.end_price = 5000;
+        escrow.start_time = 100;
+        escrow.end_time = 200;
+        escrow.token_a_amount = 400;
+        assert!(escrow.is_dutch());
+        assert_eq!(escrow.remaining(), 400);
+        assert_eq!(escrow.current_price(100), 10000);
+        assert_eq!(escrow.current_price(150), 7500);
+        assert_eq!(escrow.current_price(250), 5000);
+    }
+
+    #[test]
+    fn expire_at_floor_stops_quotes_at_end_time() {
+        let mut escrow = Escrow::new(
+            EscrowType::DutchAuction,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            10000,
+            255,
+        );
+        escrow.start_price = 10000;
+        escrow.end_price = 5000;
+        escrow.start_time = 100;
+        escrow.end_time = 200;
+        assert_eq!(escrow.quote_fill(1000, 200), Ok(Some(5000)));
+
+        escrow.expire_at_floor = 1;
+        assert_eq!(escrow.quote_fill(1000, 199), Ok(Some(5050)));
+        assert!(escrow.is_past_floor_deadline(200));
+        assert_eq!(escrow.quote_fill(1000, 200), Ok(None));
+    }
+
+    #[test]
+    fn all_or_nothing_quotes_only_the_whole_lot() {
+        let mut escrow = Escrow::new(
+            EscrowType::Partial,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            2000,
+            255,
+        );
+        assert_eq!(escrow.quote_fill(500, 0), Ok(Some(1000)));
+
+        escrow.all_or_nothing = 1;
+        assert_eq!(escrow.quote_fill(500, 0), Ok(None));
+        assert_eq!(escrow.quote_fill(1000, 0), Ok(Some(2000)));
+    }
+
+    #[test]
+    fn only_active_escrows_transition() {
+        let mut escrow = Escrow::new(
+            EscrowType::Simple,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            2000,
+            255,
+        );
+        assert_eq!(escrow.transition(Escrow::STATE_COMPLETED), Ok(()));
+        assert_eq!(
+            escrow.transition(Escrow::STATE_CANCELLED),
+            Err(EscrowErrorCode::EscrowNotActive.into())
+        );
+        assert_eq!(escrow.state, Escrow::STATE_COMPLETED);
+    }
+
+    #[test]
+    fn partial_fills_round_up_and_sum_to_the_ask() {
+        let mut escrow = Escrow::new(
+            EscrowType::Partial,
+            [1; 32],
+            [0; ESCROW_SEED_LEN],
+            [2; 32],
+            1000,
+            [3; 32],
+            3333,
+            255,
+        );
+        let mut paid = 0;
+        for fill in [333, 333, 7, 327] {
+            let token_b = escrow.partial_fill_token_b(fill).unwrap();
+            escrow.token_a_amount -= fill;
+            escrow.token_b_amount -= token_b;
+            paid += token_b;
+        }
+        assert_eq!(paid, 3333);
+        assert_eq!(escrow.token_b_amount, 0);
+    }
 }
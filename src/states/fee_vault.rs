@@ -0,0 +1,15 @@
+use pinocchio::pubkey::{self, Pubkey};
+
+/// Program-owned authority over the token accounts that collect protocol fees in one mint.
+/// Escrows route their fees there by naming the authority as their `fee_recipient`, and the
+/// config admin moves them out with `withdraw_fees`.
+pub struct FeeVault;
+
+impl FeeVault {
+    pub const PREFIX: &'static str = "FeeVault";
+
+    /// Fee vault authority for `mint` and its bump
+    pub fn find_authority(mint: &Pubkey) -> (Pubkey, u8) {
+        pubkey::find_program_address(&[Self::PREFIX.as_bytes(), mint], &crate::ID)
+    }
+}
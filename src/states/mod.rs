@@ -1,5 +1,11 @@
+pub mod config;
 pub mod escrows;
+pub mod fee_vault;
+pub mod oracle;
 pub mod utils;
 
+pub use config::*;
 pub use escrows::*;
+pub use fee_vault::*;
+pub use oracle::*;
 pub use utils::*;
@@ -0,0 +1,117 @@
+use crate::error::EscrowErrorCode;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::program_error::ProgramError;
+
+/// Price published by an oracle feed account. The account data starts with:
+///
+/// | offset | size | field                                   |
+/// |--------|------|-----------------------------------------|
+/// | 0      | 8    | `price`, little-endian `i64`            |
+/// | 8      | 4    | `exponent`, little-endian `u32`         |
+/// | 12     | 8    | `publish_time`, little-endian `i64`     |
+///
+/// One base unit of token A is worth `price / 10^exponent` base units of token B.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub exponent: u32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    pub const LEN: usize = 8 + 4 + 8;
+
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0..8].copy_from_slice(&self.price.to_le_bytes());
+        data[8..12].copy_from_slice(&self.exponent.to_le_bytes());
+        data[12..20].copy_from_slice(&self.publish_time.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let data = data
+            .get(..Self::LEN)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            price: i64::from_le_bytes(data[0..8].try_into().unwrap()),
+            exponent: u32::from_le_bytes(data[8..12].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(data[12..20].try_into().unwrap()),
+        })
+    }
+
+    /// Read the price from `oracle_account`, which must be the escrow's `oracle_feed` and owned
+    /// by `oracle_program`
+    pub fn load(
+        oracle_account: &AccountInfo,
+        oracle_feed: &[u8; 32],
+        oracle_program: &[u8; 32],
+    ) -> Result<Self, ProgramError> {
+        if oracle_account.key() != oracle_feed {
+            return Err(EscrowErrorCode::InvalidOracleFeed.into());
+        }
+        if unsafe { oracle_account.owner() } != oracle_program {
+            return Err(ProgramError::IllegalOwner);
+        }
+        Self::unpack(&oracle_account.try_borrow_data()?)
+    }
+
+    /// Reject prices published more than `max_age` seconds before `current_time`
+    pub fn check_fresh(&self, current_time: u64, max_age: u64) -> Result<(), ProgramError> {
+        let age = (current_time as i64).saturating_sub(self.publish_time);
+        if age > max_age as i64 {
+            return Err(EscrowErrorCode::StaleOraclePrice.into());
+        }
+        Ok(())
+    }
+
+    /// Token B owed for `token_a_amount` at this price, rounded up in the maker's favor
+    pub fn token_b_cost(&self, token_a_amount: u64) -> Result<u64, ProgramError> {
+        if self.price <= 0 {
+            return Err(EscrowErrorCode::PriceOutOfBounds.into());
+        }
+        let scale = 10u128
+            .checked_pow(self.exponent)
+            .ok_or(EscrowErrorCode::ArithmeticOverflow)?;
+        let cost = (token_a_amount as u128 * self.price as u128).div_ceil(scale);
+        u64::try_from(cost).map_err(|_| EscrowErrorCode::ArithmeticOverflow.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(price: i64, exponent: u32, publish_time: i64) -> OraclePrice {
+        OraclePrice {
+            price,
+            exponent,
+            publish_time,
+        }
+    }
+
+    #[test]
+    fn oracle_price_round_trips_through_bytes() {
+        let oracle = price(-25, 3, 1_700_000_000);
+        assert_eq!(OraclePrice::unpack(&oracle.pack()), Ok(oracle));
+        assert!(OraclePrice::unpack(&[0; OraclePrice::LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn token_b_cost_scales_by_exponent_and_rounds_up() {
+        // 2.5 token B per token A
+        assert_eq!(price(25, 1, 0).token_b_cost(1000), Ok(2500));
+        assert_eq!(price(25, 1, 0).token_b_cost(3), Ok(8));
+        assert!(price(0, 0, 0).token_b_cost(1000).is_err());
+    }
+
+    #[test]
+    fn stale_prices_are_rejected() {
+        let oracle = price(1, 0, 1_000);
+        assert!(oracle.check_fresh(1_060, 60).is_ok());
+        assert_eq!(
+            oracle.check_fresh(1_061, 60),
+            Err(EscrowErrorCode::StaleOraclePrice.into())
+        );
+    }
+}
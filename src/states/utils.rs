@@ -56,6 +56,35 @@ pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError>
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// SHA-256 of the concatenated byte slices, computed with the runtime syscall on-chain and
+/// with `sha2` in host builds so clients and tests get the same digest
+#[inline(always)]
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash = [0u8; 32];
+        unsafe {
+            pinocchio::syscalls::sol_sha256(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash.as_mut_ptr(),
+            );
+        }
+        hash
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+}
+
 pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
     core::slice::from_raw_parts(data as *const T as *const u8, T::LEN)
 }
@@ -64,6 +93,8 @@ pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
     core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
 }
 
+/// Read-only view of a program-owned account as `T`, checking its owner and length.
+/// Read paths should prefer this over the mutable reader so they don't hold a mutable borrow.
 pub unsafe fn try_from_account_info<T: DataLen>(acc: &AccountInfo) -> Result<&T, ProgramError> {
     if acc.owner() != &crate::ID {
         return Err(ProgramError::IllegalOwner);
@@ -76,6 +107,7 @@ pub unsafe fn try_from_account_info<T: DataLen>(acc: &AccountInfo) -> Result<&T,
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// Mutable view of a program-owned account as `T`, checking its owner and length
 pub unsafe fn try_from_account_info_mut<T: DataLen>(
     acc: &AccountInfo,
 ) -> Result<&mut T, ProgramError> {
@@ -93,3 +125,80 @@ pub unsafe fn try_from_account_info_mut<T: DataLen>(
 
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::{size_of, MaybeUninit};
+    use pinocchio::{account_info::MAX_PERMITTED_DATA_INCREASE, entrypoint::deserialize};
+
+    #[repr(C)]
+    struct Counter {
+        value: u64,
+    }
+
+    impl DataLen for Counter {
+        const LEN: usize = size_of::<Self>();
+    }
+
+    /// Serialize a single account the way the runtime does and deserialize it
+    fn with_account(owner: [u8; 32], data: &[u8], check: impl FnOnce(&AccountInfo)) {
+        let mut input = vec![0u8; 8 + 88 + data.len() + MAX_PERMITTED_DATA_INCREASE + 64];
+        input[..8].copy_from_slice(&1u64.to_le_bytes());
+        input[8] = u8::MAX; // not a duplicate
+        input[9] = 1; // signer
+        input[10] = 1; // writable
+        input[48..80].copy_from_slice(&owner);
+        input[88..96].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        input[96..96 + data.len()].copy_from_slice(data);
+
+        // Back the buffer with u64s so the runtime's 8-byte alignment holds
+        let mut aligned = vec![0u64; input.len().div_ceil(8)];
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                input.as_ptr(),
+                aligned.as_mut_ptr() as *mut u8,
+                input.len(),
+            );
+            let mut accounts = [const { MaybeUninit::<AccountInfo>::uninit() }; 1];
+            deserialize::<1>(aligned.as_mut_ptr() as *mut u8, &mut accounts);
+            check(accounts[0].assume_init_ref());
+        }
+    }
+
+    #[test]
+    fn readers_accept_program_owned_account_of_the_right_length() {
+        with_account(crate::ID, &7u64.to_le_bytes(), |account| unsafe {
+            try_from_account_info_mut::<Counter>(account).unwrap().value += 1;
+            assert_eq!(try_from_account_info::<Counter>(account).unwrap().value, 8);
+        });
+    }
+
+    #[test]
+    fn readers_reject_foreign_owner() {
+        with_account([9; 32], &7u64.to_le_bytes(), |account| unsafe {
+            assert_eq!(
+                try_from_account_info::<Counter>(account).err(),
+                Some(ProgramError::IllegalOwner)
+            );
+            assert_eq!(
+                try_from_account_info_mut::<Counter>(account).err(),
+                Some(ProgramError::IllegalOwner)
+            );
+        });
+    }
+
+    #[test]
+    fn readers_reject_wrong_length() {
+        with_account(crate::ID, &[0; 4], |account| unsafe {
+            assert_eq!(
+                try_from_account_info::<Counter>(account).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+            assert_eq!(
+                try_from_account_info_mut::<Counter>(account).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        });
+    }
+}
@@ -0,0 +1,123 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_pubkey::pubkey;
+use pinocchio_token::state::{Mint, TokenAccount};
+
+/// Token-2022 program. Token A may be a Token-2022 mint without extensions, whose accounts
+/// share the classic layout and accept the classic `Transfer` and `CloseAccount` instructions.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Token-2022 account type byte that follows the base layout of accounts with extensions
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Whether `program` is the classic token program or Token-2022
+pub fn is_token_program(program: &Pubkey) -> bool {
+    program == &pinocchio_token::ID || program == &TOKEN_2022_PROGRAM_ID
+}
+
+/// Read a token account owned by either token program. Token-2022 accounts may carry
+/// extensions (such as the immutable owner its associated token accounts get) after the
+/// base layout.
+///
+/// # Safety
+///
+/// The caller must ensure the account data is not mutably borrowed elsewhere.
+pub unsafe fn token_account_unchecked(
+    account_info: &AccountInfo,
+) -> Result<&TokenAccount, ProgramError> {
+    let owner = account_info.owner();
+    if !is_token_program(owner) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let data = account_info.borrow_data_unchecked();
+    let has_extensions = owner == &TOKEN_2022_PROGRAM_ID
+        && data.len() > TokenAccount::LEN
+        && data[TokenAccount::LEN] == ACCOUNT_TYPE_ACCOUNT;
+    if data.len() != TokenAccount::LEN && !has_extensions {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(TokenAccount::from_bytes(data))
+}
+
+/// Read a mint owned by either token program. Token-2022 mints with extensions are rejected,
+/// since transfer fees and hooks would need instructions the escrow doesn't send.
+///
+/// # Safety
+///
+/// The caller must ensure the account data is not mutably borrowed elsewhere.
+pub unsafe fn mint_unchecked(account_info: &AccountInfo) -> Result<&Mint, ProgramError> {
+    if !is_token_program(account_info.owner()) || account_info.data_len() != Mint::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(Mint::from_bytes(account_info.borrow_data_unchecked()))
+}
+
+/// `Transfer` sent to `token_program`, which may be either token program
+pub struct Transfer<'a> {
+    pub token_program: &'a Pubkey,
+    pub from: &'a AccountInfo,
+    pub to: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub amount: u64,
+}
+
+impl Transfer<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.from.key()),
+            AccountMeta::writable(self.to.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        // Discriminator, then the amount
+        let mut data = [0u8; 9];
+        data[0] = 3;
+        data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &data,
+        };
+        invoke_signed(&instruction, &[self.from, self.to, self.authority], signers)
+    }
+}
+
+/// `CloseAccount` sent to `token_program`, which may be either token program
+pub struct CloseAccount<'a> {
+    pub token_program: &'a Pubkey,
+    pub account: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+}
+
+impl CloseAccount<'_> {
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::writable(self.destination.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &[9],
+        };
+        invoke_signed(
+            &instruction,
+            &[self.account, self.destination, self.authority],
+            signers,
+        )
+    }
+}
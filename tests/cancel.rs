@@ -0,0 +1,348 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::MakeEscrowIx,
+    states::{Escrow, EscrowType},
+};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_cancel_simple_escrow_refunds_maker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 4000;
+    let token_b_amount = 6000;
+
+    println!("=== Testing Cancel Simple Escrow ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - token_a_amount);
+
+    setup.cancel_escrow()?;
+
+    assert_eq!(
+        setup.get_maker_token_a_balance(),
+        10000,
+        "Maker should be refunded all token A"
+    );
+    assert!(
+        setup
+            .svm
+            .get_account(&setup.escrow_pda)
+            .is_none_or(|a| a.data.is_empty()),
+        "Escrow account should be closed"
+    );
+    assert!(
+        setup
+            .svm
+            .get_account(&setup.escrow_token_a_ata)
+            .is_none_or(|a| a.data.is_empty()),
+        "Escrow token A account should be closed"
+    );
+
+    println!("✅ Cancel simple escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_replace_simple_escrow_then_take() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 4000;
+    let old_token_b_amount = 8000;
+    let new_token_b_amount = 6000;
+
+    println!("=== Testing Replace Simple Escrow ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, old_token_b_amount)?;
+    let old_escrow_pda = setup.escrow_pda;
+
+    // Reprice under a new seed
+    let new_seed = 1u64.to_le_bytes();
+    let (_, new_bump) = Pubkey::find_program_address(
+        &[b"Escrow", setup.maker.pubkey().as_ref(), &new_seed],
+        &setup.program_id,
+    );
+    let ix = MakeEscrowIx::new(
+        EscrowType::Simple,
+        token_a_amount,
+        new_token_b_amount,
+        new_bump,
+        new_seed,
+    );
+    setup.replace_escrow(ix)?;
+
+    assert!(
+        setup
+            .svm
+            .get_account(&old_escrow_pda)
+            .is_none_or(|a| a.data.is_empty()),
+        "Old escrow account should be closed"
+    );
+    assert_eq!(
+        setup.get_maker_token_a_balance(),
+        10000 - token_a_amount,
+        "Token A should move between escrows without returning to the maker"
+    );
+    assert_eq!(setup.get_escrow_token_a_balance(), token_a_amount);
+
+    // The replacement trades at the new price
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(token_a_amount, new_token_b_amount, "after_take")?;
+
+    println!("✅ Replace simple escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_batch_cancel_refunds_every_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Batch Cancel ===");
+
+    let mut escrows = Vec::new();
+    for (seed, amount) in [(1u64, 1000), (2, 1500), (3, 2000)] {
+        setup.use_seed(seed.to_le_bytes())?;
+        setup.create_escrow(EscrowType::Simple, amount, 2 * amount)?;
+        escrows.push(setup.escrow_pda);
+    }
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 4500);
+
+    // A seed with no escrow behind it is skipped
+    setup.batch_cancel(&[1u64, 2, 9, 3].map(u64::to_le_bytes))?;
+
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    for escrow in escrows {
+        assert!(setup.svm.get_account(&escrow).is_none());
+    }
+
+    println!("✅ Batch cancel test passed");
+    Ok(())
+}
+
+#[test]
+fn test_cancel_refunds_designated_refund_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Cancel To Designated Refund Account ===");
+
+    let cold_wallet = Pubkey::new_unique();
+    let token_a_mint = setup.token_a_mint;
+    let payer = setup.maker.insecure_clone();
+    let refund_account = setup_token_account(&mut setup.svm, &token_a_mint, &cold_wallet, &payer)?;
+
+    let mut ix = MakeEscrowIx::new(EscrowType::Simple, 3000, 6000, setup.bump, setup.seed);
+    ix.refund_account = refund_account.to_bytes();
+    setup.create_escrow_with_ix(ix)?;
+
+    // The maker's own token account is no longer an accepted refund destination
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidRefundAccount)?;
+
+    let maker_token_a_ata = setup.maker_token_a_ata;
+    setup.maker_token_a_ata = refund_account;
+    setup.cancel_escrow()?;
+    setup.maker_token_a_ata = maker_token_a_ata;
+
+    assert_eq!(setup.get_token_account_balance(&refund_account), 3000);
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 3000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    println!("✅ Cancel to designated refund account test passed");
+    Ok(())
+}
+
+#[test]
+fn test_replace_refunds_leftover_to_designated_refund_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let cold_wallet = Pubkey::new_unique();
+    let token_a_mint = setup.token_a_mint;
+    let payer = setup.maker.insecure_clone();
+    let refund_account = setup_token_account(&mut setup.svm, &token_a_mint, &cold_wallet, &payer)?;
+
+    let mut ix = MakeEscrowIx::new(EscrowType::Simple, 3000, 6000, setup.bump, setup.seed);
+    ix.refund_account = refund_account.to_bytes();
+    setup.create_escrow_with_ix(ix)?;
+
+    let replacement = |setup: &EscrowTestSetup, seed: u64| {
+        let seed = seed.to_le_bytes();
+        let (_, bump) = Pubkey::find_program_address(
+            &[b"Escrow", setup.maker.pubkey().as_ref(), &seed],
+            &setup.program_id,
+        );
+        MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, bump, seed)
+    };
+
+    // The 2000 token A left behind may not go to the maker's own token account
+    let ix = replacement(&setup, 1);
+    let result = setup.replace_escrow(ix);
+    assert_escrow_error(result, EscrowErrorCode::InvalidRefundAccount)?;
+
+    let maker_token_a_ata = setup.maker_token_a_ata;
+    setup.maker_token_a_ata = refund_account;
+    let ix = replacement(&setup, 2);
+    setup.replace_escrow(ix)?;
+    setup.maker_token_a_ata = maker_token_a_ata;
+
+    assert_eq!(setup.get_token_account_balance(&refund_account), 2000);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 3000);
+
+    Ok(())
+}
+
+#[test]
+fn test_cancel_and_withdraw_reject_a_token_account_other_than_the_vault() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Partial, 1000, 2000)?;
+
+    // A second token A account the escrow PDA also owns, holding someone's stray deposit
+    let (token_a_mint, escrow_pda) = (setup.token_a_mint, setup.escrow_pda);
+    let payer = setup.maker.insecure_clone();
+    let decoy = setup_token_account(&mut setup.svm, &token_a_mint, &escrow_pda, &payer)?;
+    mint_to(&mut setup.svm, &token_a_mint, &payer, &decoy, 500)?;
+
+    let vault = setup.escrow_token_a_ata;
+    setup.escrow_token_a_ata = decoy;
+    let result = setup.withdraw_escrow(100);
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+    setup.escrow_token_a_ata = vault;
+
+    assert_eq!(setup.get_token_account_balance(&decoy), 500);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_close_drained_escrow_refunds_stray_tokens() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let result = setup.close_escrow();
+    assert_escrow_error(result, EscrowErrorCode::EscrowNotEmpty)?;
+
+    // Stray tokens in the vault keep the escrow open after the take drains it
+    let (token_a_mint, escrow_token_a_ata) = (setup.token_a_mint, setup.escrow_token_a_ata);
+    mint_to(
+        &mut setup.svm,
+        &token_a_mint,
+        &setup.maker,
+        &escrow_token_a_ata,
+        5,
+    )?;
+    setup.take_escrow()?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_some());
+
+    setup.svm.expire_blockhash();
+    setup.close_escrow()?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 1000 + 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_completed_escrow_cannot_be_taken_or_cancelled() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Stray tokens keep the completed escrow open after the take
+    let (token_a_mint, escrow_token_a_ata) = (setup.token_a_mint, setup.escrow_token_a_ata);
+    mint_to(
+        &mut setup.svm,
+        &token_a_mint,
+        &setup.maker,
+        &escrow_token_a_ata,
+        5,
+    )?;
+    setup.take_escrow()?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_some());
+    assert_eq!(
+        setup.get_escrow(&setup.escrow_pda)?.state,
+        Escrow::STATE_COMPLETED
+    );
+
+    setup.svm.expire_blockhash();
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::EscrowNotActive)?;
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::EscrowNotActive)?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_cancelled_escrow_cannot_be_taken() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    setup.cancel_escrow()?;
+
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowOwner)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+/// A partial escrow of 2000 token A for 4000 token B that must raise 2000 token B by `deadline`
+fn make_floor_escrow(setup: &mut EscrowTestSetup, deadline: u64) -> Result<()> {
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        proceeds_floor: 2000,
+        proceeds_deadline: deadline,
+        ..MakeEscrowIx::new(EscrowType::Partial, 2000, 4000, setup.bump, setup.seed)
+    })
+}
+
+#[test]
+fn test_cancel_blocked_until_proceeds_floor_met() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.set_time(1_000_000)?;
+    make_floor_escrow(&mut setup, 1_000_000 + 3600)?;
+
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::ProceedsFloorNotMet)?;
+    let result = setup.withdraw_escrow(500);
+    assert_escrow_error(result, EscrowErrorCode::ProceedsFloorNotMet)?;
+
+    // Half the lot pays exactly the floor, unlocking the rest
+    setup.take_partial_escrow(1000)?;
+    setup.svm.expire_blockhash();
+    setup.cancel_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 1000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_cancel_allowed_after_proceeds_deadline() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.set_time(1_000_000)?;
+    make_floor_escrow(&mut setup, 1_000_000 + 3600)?;
+    setup.take_partial_escrow(500)?;
+
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::ProceedsFloorNotMet)?;
+
+    // Past the deadline the maker gets the unsold token A back
+    setup.advance_time(3600)?;
+    setup.svm.expire_blockhash();
+    setup.cancel_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 500);
+
+    Ok(())
+}
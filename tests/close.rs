@@ -0,0 +1,108 @@
+use anyhow::Result;
+use escrow_suite::{instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::signer::Signer;
+
+mod common;
+pub use common::*;
+
+/// Fee the maker pays for a transaction with one signature
+const MAKER_TX_FEE: u64 = 5000;
+
+/// Lamports held by the escrow PDA and its token A account
+fn escrow_rent(setup: &EscrowTestSetup) -> u64 {
+    setup.svm.get_balance(&setup.escrow_pda).unwrap_or(0)
+        + setup
+            .svm
+            .get_balance(&setup.escrow_token_a_ata)
+            .unwrap_or(0)
+}
+
+fn maker_lamports(setup: &EscrowTestSetup) -> u64 {
+    setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0)
+}
+
+/// Check both escrow accounts are gone, then make a new escrow at the same seed
+fn assert_closed_and_recreatable(setup: &mut EscrowTestSetup) -> Result<()> {
+    for address in [setup.escrow_pda, setup.escrow_token_a_ata] {
+        if let Some(account) = setup.svm.get_account(&address) {
+            assert!(account.data.is_empty(), "{} still holds data", address);
+            assert_eq!(account.lamports, 0, "{} still holds lamports", address);
+        }
+    }
+
+    let seed = setup.seed;
+    setup.use_seed(seed)?;
+    setup.svm.expire_blockhash();
+    setup.create_escrow(EscrowType::Simple, 500, 1000)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 500);
+    Ok(())
+}
+
+#[test]
+fn test_cancel_closes_escrow_accounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let rent = escrow_rent(&setup);
+    let lamports_before = maker_lamports(&setup);
+
+    setup.cancel_escrow()?;
+
+    assert_eq!(
+        maker_lamports(&setup),
+        lamports_before + rent - MAKER_TX_FEE
+    );
+    assert_closed_and_recreatable(&mut setup)
+}
+
+#[test]
+fn test_full_take_closes_escrow_accounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let rent = escrow_rent(&setup);
+    let lamports_before = maker_lamports(&setup);
+
+    setup.take_escrow()?;
+
+    assert_eq!(maker_lamports(&setup), lamports_before + rent);
+    assert_closed_and_recreatable(&mut setup)
+}
+
+#[test]
+fn test_final_partial_take_closes_escrow_accounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Partial, 1000, 2000)?;
+    setup.take_partial_escrow(400)?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_some());
+
+    let rent = escrow_rent(&setup);
+    let lamports_before = maker_lamports(&setup);
+
+    setup.svm.expire_blockhash();
+    setup.take_partial_escrow(600)?;
+
+    assert_eq!(maker_lamports(&setup), lamports_before + rent);
+    assert_closed_and_recreatable(&mut setup)
+}
+
+#[test]
+fn test_finalize_expired_closes_escrow_accounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expire_at_floor: true,
+        ..MakeEscrowIx::new_dutch_auction(1000, 2000, 1000, 0, 600, setup.bump, setup.seed)
+    })?;
+    setup.advance_time(600)?;
+
+    let rent = escrow_rent(&setup);
+    let lamports_before = maker_lamports(&setup);
+
+    setup.finalize_escrow()?;
+
+    assert_eq!(maker_lamports(&setup), lamports_before + rent);
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    assert_closed_and_recreatable(&mut setup)
+}
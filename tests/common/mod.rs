@@ -1,10 +1,22 @@
 use anyhow::Result;
-use escrow_suite::{instructions::MakeEscrowIx, states::EscrowType, ID};
-use litesvm::LiteSVM;
-use litesvm_token::{spl_token, CreateAssociatedTokenAccount, CreateMint, MintTo};
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{
+        DepositEscrowIx, MakeEscrowIx, PlaceBidIx, SetConfigIx, TakeBatchEntry, TakeBestIx,
+        TakeLimitIx, UpdateEscrowIx,
+    },
+    states::{Escrow, EscrowType},
+    ID,
+};
+use litesvm::{types::TransactionMetadata, LiteSVM};
+use litesvm_token::{spl_token, CreateAccount, CreateAssociatedTokenAccount, CreateMint, MintTo};
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    account::Account,
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction, InstructionError},
     message::{v0, VersionedMessage},
+    program_pack::Pack,
+    pubkey,
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
@@ -27,14 +39,110 @@ pub fn setup_svm_and_program() -> (LiteSVM, Keypair, Pubkey) {
 }
 
 pub fn setup_mint(svm: &mut LiteSVM, payer: &Keypair) -> anyhow::Result<Pubkey> {
+    setup_mint_with_decimals(svm, payer, 9)
+}
+
+pub fn setup_mint_with_decimals(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    decimals: u8,
+) -> anyhow::Result<Pubkey> {
     let mint = CreateMint::new(svm, payer)
-        .decimals(9)
+        .decimals(decimals)
         .token_program_id(&spl_token::ID)
         .send()
         .map_err(|e| anyhow::anyhow!("Failed to create mint {:?}", e))?;
     Ok(mint)
 }
 
+/// Token-2022 program, loaded by LiteSVM alongside the classic token program
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Write an initialized Token-2022 mint account without extensions straight into the SVM
+pub fn setup_token_2022_mint(svm: &mut LiteSVM, authority: &Pubkey, decimals: u8) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: Some(*authority).into(),
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    }
+    .pack_into_slice(&mut data);
+
+    svm.set_account(
+        mint,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: TOKEN_2022_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .expect("mint account is valid");
+    mint
+}
+
+/// Write an initialized Token-2022 token account without extensions holding `amount` of `mint`
+pub fn setup_token_2022_account(
+    svm: &mut LiteSVM,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let token_account = Pubkey::new_unique();
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+
+    svm.set_account(
+        token_account,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: TOKEN_2022_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .expect("token account is valid");
+    token_account
+}
+
+/// Write the wrapped SOL mint, which LiteSVM doesn't create by default
+pub fn setup_native_mint(svm: &mut LiteSVM) -> Pubkey {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: None.into(),
+        supply: 0,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    }
+    .pack_into_slice(&mut data);
+
+    svm.set_account(
+        spl_token::native_mint::ID,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .expect("native mint account is valid");
+    spl_token::native_mint::ID
+}
+
 pub fn setup_ata(
     svm: &mut LiteSVM,
     mint: &Pubkey,
@@ -47,6 +155,19 @@ pub fn setup_ata(
         .map_err(|_| anyhow::anyhow!("Failed to create associated token account"))
 }
 
+/// Create an additional (non-associated) token account for `user`
+pub fn setup_token_account(
+    svm: &mut LiteSVM,
+    mint: &Pubkey,
+    user: &Pubkey,
+    payer: &Keypair,
+) -> Result<Pubkey, anyhow::Error> {
+    CreateAccount::new(svm, payer, mint)
+        .owner(user)
+        .send()
+        .map_err(|e| anyhow::anyhow!("Failed to create token account {:?}", e))
+}
+
 pub fn mint_to(
     svm: &mut LiteSVM,
     mint: &Pubkey,
@@ -60,6 +181,37 @@ pub fn mint_to(
     Ok(())
 }
 
+/// Assert that a transaction failed with the given custom program error
+pub fn assert_escrow_error(result: Result<()>, expected: EscrowErrorCode) -> Result<()> {
+    match result {
+        Ok(_) => Err(anyhow::anyhow!(
+            "Expected {:?} but transaction succeeded",
+            expected
+        )),
+        Err(e) => {
+            let needle = format!("Custom({})", expected as u32);
+            if format!("{:?}", e).contains(&needle) {
+                println!("Expected error ({:?}): {:?}", expected, e);
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("Expected {:?} but got {:?}", expected, e))
+            }
+        }
+    }
+}
+
+/// Assert that a transaction failed with the given built-in program error
+pub fn assert_instruction_error(result: Result<()>, expected: InstructionError) -> Result<()> {
+    match result {
+        Ok(_) => Err(anyhow::anyhow!(
+            "Expected {:?} but transaction succeeded",
+            expected
+        )),
+        Err(e) if format!("{:?}", e).contains(&format!("{:?}", expected)) => Ok(()),
+        Err(e) => Err(anyhow::anyhow!("Expected {:?} but got {:?}", expected, e)),
+    }
+}
+
 pub fn display_user_balance_and_ata_balance(
     svm: &LiteSVM,
     user: &Pubkey,
@@ -116,16 +268,21 @@ pub struct EscrowTestSetup {
     pub escrow_pda: Pubkey,
     pub escrow_token_a_ata: Pubkey,
     pub bump: u8,
-    pub seed: [u8; 2],
+    pub seed: [u8; 8],
 }
 
 impl EscrowTestSetup {
     pub fn new() -> Result<Self> {
+        Self::with_decimals(9, 9)
+    }
+
+    /// Set up with token A and token B mints of the given decimals
+    pub fn with_decimals(token_a_decimals: u8, token_b_decimals: u8) -> Result<Self> {
         let (mut svm, maker, program_id) = setup_svm_and_program();
 
-        let token_a_mint = setup_mint(&mut svm, &maker)
+        let token_a_mint = setup_mint_with_decimals(&mut svm, &maker, token_a_decimals)
             .map_err(|e| anyhow::anyhow!("Failed to setup mint: {:?}", e))?;
-        let token_b_mint = setup_mint(&mut svm, &maker)
+        let token_b_mint = setup_mint_with_decimals(&mut svm, &maker, token_b_decimals)
             .map_err(|e| anyhow::anyhow!("Failed to setup mint: {:?}", e))?;
 
         let maker_token_a_ata = setup_ata(&mut svm, &token_a_mint, &maker.pubkey(), &maker)
@@ -139,7 +296,7 @@ impl EscrowTestSetup {
         mint_to(&mut svm, &token_b_mint, &maker, &maker_token_b_ata, 10000)
             .map_err(|e| anyhow::anyhow!("Failed to mint tokens: {:?}", e))?;
 
-        let seed: [u8; 2] = [0, 0];
+        let seed = [0u8; 8];
         let (escrow_pda, bump) =
             Pubkey::find_program_address(&[b"Escrow", maker.pubkey().as_ref(), &seed], &program_id);
 
@@ -180,23 +337,74 @@ impl EscrowTestSetup {
         })
     }
 
+    /// Point the setup at the maker's escrow for `seed`, creating its token A account
+    pub fn use_seed(&mut self, seed: [u8; 8]) -> Result<()> {
+        let (escrow_pda, bump) = Pubkey::find_program_address(
+            &[b"Escrow", self.maker.pubkey().as_ref(), &seed],
+            &self.program_id,
+        );
+        self.escrow_token_a_ata =
+            setup_ata(&mut self.svm, &self.token_a_mint, &escrow_pda, &self.maker)?;
+        self.escrow_pda = escrow_pda;
+        self.bump = bump;
+        self.seed = seed;
+        Ok(())
+    }
+
     pub fn create_escrow(
         &mut self,
         escrow_type: EscrowType,
         token_a_amount: u64,
         token_b_amount: u64,
     ) -> Result<()> {
-        let mut ix_data = [0u8; MakeEscrowIx::LEN + 1];
-        ix_data[0] = 0x01;
-
-        let ix = MakeEscrowIx::new(
+        let mut ix = MakeEscrowIx::new(
             escrow_type,
             token_a_amount,
             token_b_amount,
             self.bump,
             self.seed,
         );
+        // Oracle escrows must name their price feed; tests that take them set a real one
+        if escrow_type == EscrowType::Oracle {
+            ix.oracle_feed = Pubkey::new_unique().to_bytes();
+        }
+
+        self.create_escrow_with_ix(ix)
+    }
+
+    pub fn create_dutch_auction_escrow(
+        &mut self,
+        token_a_amount: u64,
+        start_price: u64,
+        end_price: u64,
+        duration: u64,
+    ) -> Result<()> {
+        // Built directly rather than through the builder so tests can send invalid auctions
+        let ix = MakeEscrowIx {
+            end_price,
+            duration,
+            ..MakeEscrowIx::new(
+                EscrowType::DutchAuction,
+                token_a_amount,
+                start_price,
+                self.bump,
+                self.seed,
+            )
+        };
+
+        self.create_escrow_with_ix(ix)
+    }
+
+    /// Create an escrow from a fully specified make instruction
+    pub fn create_escrow_with_ix(&mut self, ix: MakeEscrowIx) -> Result<()> {
+        let instruction = self.make_instruction(ix);
+        self.send_maker_instruction(instruction.data, instruction.accounts)
+    }
 
+    /// Build a make instruction for the setup's escrow accounts
+    pub fn make_instruction(&self, ix: MakeEscrowIx) -> Instruction {
+        let mut ix_data = [0u8; MakeEscrowIx::LEN + 1];
+        ix_data[0] = 0x01;
         ix_data[1..].copy_from_slice(&ix.pack());
 
         let accounts = vec![
@@ -208,73 +416,382 @@ impl EscrowTestSetup {
             AccountMeta::new_readonly(self.token_b_mint, false),
             AccountMeta::new(self.program_id, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
             AccountMeta::new_readonly(spl_token::ID, false),
         ];
 
-        let instruction = Instruction {
+        Instruction {
             program_id: self.program_id,
             accounts,
             data: ix_data.to_vec(),
-        };
+        }
+    }
 
-        let msg = v0::Message::try_compile(
-            &self.maker.pubkey(),
-            &[instruction],
-            &[],
-            self.svm.latest_blockhash(),
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to compile message: {:?}", e))?;
+    /// Build a make instruction where the setup's maker pays and funds token A for an
+    /// escrow owned by `authorized_maker`, who must authorize it via a preceding ed25519 instruction
+    pub fn make_authorized_instruction(
+        &self,
+        ix: MakeEscrowIx,
+        authorized_maker: &Pubkey,
+    ) -> Instruction {
+        let mut ix_data = vec![0x10];
+        ix_data.extend_from_slice(&ix.pack());
 
-        let tx = VersionedTransaction::try_new(
-            VersionedMessage::V0(msg),
-            &[self.maker.insecure_clone()],
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create transaction: {:?}", e))?;
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new_readonly(self.token_a_mint, false),
+            AccountMeta::new_readonly(self.token_b_mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(*authorized_maker, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
 
-        self.svm
-            .send_transaction(tx)
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
-        Ok(())
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: ix_data,
+        }
     }
 
-    pub fn create_dutch_auction_escrow(
+    /// Cancel the maker's escrows for each of `seeds` in one instruction
+    pub fn batch_cancel(&mut self, seeds: &[[u8; 8]]) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        let mut ix_data = vec![0x14];
+        for seed in seeds {
+            let (escrow_pda, _) = Pubkey::find_program_address(
+                &[b"Escrow", self.maker.pubkey().as_ref(), seed],
+                &self.program_id,
+            );
+            accounts.push(AccountMeta::new(escrow_pda, false));
+            accounts.push(AccountMeta::new(
+                get_associated_token_address(&escrow_pda, &self.token_a_mint),
+                false,
+            ));
+            ix_data.extend_from_slice(seed);
+        }
+
+        self.send_maker_instruction(ix_data, accounts)
+    }
+
+    /// Finalize the escrow as the taker (any account may), returning the transaction logs
+    pub fn finalize_escrow(&mut self) -> Result<Vec<String>> {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.escrow_pda, false),
+                AccountMeta::new(self.escrow_token_a_ata, false),
+                AccountMeta::new(self.maker.pubkey(), false),
+                AccountMeta::new(self.maker_token_a_ata, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: vec![0x16],
+        };
+        let taker = self.taker.insecure_clone();
+        self.send_instructions_with_logs(&[instruction], &[&taker])
+    }
+
+    /// Refund and close the expired escrow, signed and paid for by `caller`
+    pub fn reclaim_expired(&mut self, caller: &Keypair) -> Result<Vec<String>> {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.escrow_pda, false),
+                AccountMeta::new(self.escrow_token_a_ata, false),
+                AccountMeta::new(self.maker.pubkey(), false),
+                AccountMeta::new(self.maker_token_a_ata, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: vec![0x0B],
+        };
+        self.send_instructions_with_logs(&[instruction], &[caller])
+    }
+
+    /// Create the escrow's token B account that holds English auction bids
+    pub fn setup_escrow_token_b_ata(&mut self) -> Result<Pubkey> {
+        let token_b_mint = self.token_b_mint;
+        let escrow_pda = self.escrow_pda;
+        setup_ata(&mut self.svm, &token_b_mint, &escrow_pda, &self.maker)
+    }
+
+    /// Bid `amount` of `bidder`'s token B, refunding the previous bid to `previous_bidder_token_b_ata`
+    pub fn place_bid(
         &mut self,
-        token_a_amount: u64,
-        start_price: u64,
-        end_price: u64,
-        duration: u64,
+        bidder: &Keypair,
+        previous_bidder_token_b_ata: Pubkey,
+        amount: u64,
     ) -> Result<()> {
-        let mut ix_data = [0u8; MakeEscrowIx::LEN + 1];
-        ix_data[0] = 0x01;
+        let mut data = vec![0x06];
+        data.extend_from_slice(&PlaceBidIx { amount }.pack());
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(bidder.pubkey(), true),
+                AccountMeta::new(
+                    get_associated_token_address(&bidder.pubkey(), &self.token_b_mint),
+                    false,
+                ),
+                AccountMeta::new(self.escrow_pda, false),
+                AccountMeta::new(
+                    get_associated_token_address(&self.escrow_pda, &self.token_b_mint),
+                    false,
+                ),
+                AccountMeta::new(previous_bidder_token_b_ata, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data,
+        };
+        self.send_instructions(&[instruction], &[bidder])
+    }
 
-        let ix = MakeEscrowIx {
-            escrow_type: EscrowType::DutchAuction,
+    /// Settle an English auction, paying token A to `winner_token_a_ata`
+    pub fn settle_auction(&mut self, winner_token_a_ata: Pubkey) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.escrow_pda, false),
+                AccountMeta::new(self.escrow_token_a_ata, false),
+                AccountMeta::new(
+                    get_associated_token_address(&self.escrow_pda, &self.token_b_mint),
+                    false,
+                ),
+                AccountMeta::new(self.maker.pubkey(), false),
+                AccountMeta::new(self.maker_token_b_ata, false),
+                AccountMeta::new(winner_token_a_ata, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: vec![0x1C],
+        };
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[instruction], &[&taker])
+    }
+
+    /// Withdraw `token_a_amount` of the escrow's remaining token A back to the maker
+    pub fn withdraw_escrow(&mut self, token_a_amount: u64) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+
+        let mut ix_data = vec![0x13];
+        ix_data.extend_from_slice(&token_a_amount.to_le_bytes());
+        self.send_maker_instruction(ix_data, accounts)
+    }
+
+    /// Sweep the token B held by `escrow_token_b_ata` to the maker's token B account
+    pub fn sweep_escrow(&mut self, escrow_token_b_ata: Pubkey) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_b_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(escrow_token_b_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+
+        self.send_maker_instruction(vec![0x0A], accounts)
+    }
+
+    /// Add token A to the escrow, raising its ask by `token_b_amount`
+    pub fn deposit_escrow(&mut self, token_a_amount: u64, token_b_amount: u64) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+
+        let ix = DepositEscrowIx {
             token_a_amount,
-            token_b_amount: start_price, // Use start_price as token_b_amount
-            seed: self.seed,
-            bump: self.bump,
-            end_price,
-            duration,
+            token_b_amount,
         };
+        let mut ix_data = vec![0x07];
+        ix_data.extend_from_slice(&ix.pack());
+        self.send_maker_instruction(ix_data, accounts)
+    }
 
-        ix_data[1..].copy_from_slice(&ix.pack());
+    /// Enable or disable fills on the partial escrow
+    pub fn set_fills_enabled(&mut self, fills_enabled: bool) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.escrow_pda, false),
+        ];
+
+        self.send_maker_instruction(vec![0x1A, fills_enabled as u8], accounts)
+    }
+
+    /// Change the escrow's asking price
+    pub fn update_escrow(&mut self, ix: UpdateEscrowIx) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
+        ];
+
+        let mut ix_data = vec![0x05];
+        ix_data.extend_from_slice(&ix.pack());
+        self.send_maker_instruction(ix_data, accounts)
+    }
+
+    /// Restart the Dutch auction's decay from `start_price`
+    pub fn reprice_dutch(&mut self, start_price: u64) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.escrow_pda, false),
+        ];
+
+        let mut ix_data = vec![0x1B];
+        ix_data.extend_from_slice(&start_price.to_le_bytes());
+        self.send_maker_instruction(ix_data, accounts)
+    }
+
+    /// Close the drained escrow, refunding any stray token A to the maker
+    pub fn close_escrow(&mut self) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+
+        self.send_maker_instruction(vec![0x04], accounts)
+    }
+
+    /// Cancel the escrow, refunding its token A to the maker
+    pub fn cancel_escrow(&mut self) -> Result<()> {
+        let accounts = vec![
+            AccountMeta::new(self.maker.pubkey(), true),
+            AccountMeta::new(self.maker_token_a_ata, false),
+            AccountMeta::new(self.escrow_pda, false),
+            AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+
+        self.send_maker_instruction(vec![0x03], accounts)
+    }
+
+    /// Replace the escrow with the one described by `ix` (at `ix.seed`), then
+    /// point the setup at the replacement
+    pub fn replace_escrow(&mut self, ix: MakeEscrowIx) -> Result<()> {
+        let (new_escrow_pda, _) = Pubkey::find_program_address(
+            &[b"Escrow", self.maker.pubkey().as_ref(), &ix.seed],
+            &self.program_id,
+        );
+        let new_escrow_token_a_ata = setup_ata(
+            &mut self.svm,
+            &self.token_a_mint,
+            &new_escrow_pda,
+            &self.maker,
+        )?;
+
+        let mut ix_data = vec![0x0E];
+        ix_data.extend_from_slice(&ix.pack());
 
         let accounts = vec![
             AccountMeta::new(self.maker.pubkey(), true),
             AccountMeta::new(self.maker_token_a_ata, false),
             AccountMeta::new(self.escrow_pda, false),
             AccountMeta::new(self.escrow_token_a_ata, false),
+            AccountMeta::new(new_escrow_pda, false),
+            AccountMeta::new(new_escrow_token_a_ata, false),
             AccountMeta::new_readonly(self.token_a_mint, false),
             AccountMeta::new_readonly(self.token_b_mint, false),
-            AccountMeta::new(self.program_id, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
         ];
 
+        self.send_maker_instruction(ix_data, accounts)?;
+
+        self.escrow_pda = new_escrow_pda;
+        self.escrow_token_a_ata = new_escrow_token_a_ata;
+        self.bump = ix.bump;
+        self.seed = ix.seed;
+        Ok(())
+    }
+
+    /// The program config PDA
+    pub fn config_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"Config"], &self.program_id).0
+    }
+
+    /// The program's ProgramData account under the upgradeable loader
+    pub fn program_data_address(&self) -> Pubkey {
+        bpf_loader_upgradeable::get_program_data_address(&self.program_id)
+    }
+
+    /// Install a ProgramData account naming `authority` as the program's upgrade authority
+    pub fn set_upgrade_authority(&mut self, authority: &Pubkey) -> Result<()> {
+        let mut data = Vec::with_capacity(45);
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(authority.as_ref());
+
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                self.program_data_address(),
+                Account {
+                    lamports,
+                    data,
+                    owner: bpf_loader_upgradeable::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to set ProgramData account: {:?}", e))
+    }
+
+    /// A `set_config` instruction signed by `admin`
+    pub fn set_config_instruction(&self, admin: &Pubkey, ix: SetConfigIx) -> Instruction {
+        let mut data = vec![0x0F];
+        data.extend_from_slice(&ix.pack());
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*admin, true),
+                AccountMeta::new(self.config_pda(), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(self.program_data_address(), false),
+            ],
+            data,
+        }
+    }
+
+    /// Create or update the program config with the maker as admin and upgrade authority
+    pub fn set_config(&mut self, ix: SetConfigIx) -> Result<()> {
+        let maker = self.maker.pubkey();
+        self.set_upgrade_authority(&maker)?;
+
+        let instruction = self.set_config_instruction(&maker, ix);
+        let maker = self.maker.insecure_clone();
+        self.send_instructions(&[instruction], &[&maker])
+    }
+
+    /// Send a program instruction signed by the maker
+    pub fn send_maker_instruction(
+        &mut self,
+        ix_data: Vec<u8>,
+        accounts: Vec<AccountMeta>,
+    ) -> Result<()> {
         let instruction = Instruction {
             program_id: self.program_id,
             accounts,
-            data: ix_data.to_vec(),
+            data: ix_data,
         };
 
         let msg = v0::Message::try_compile(
@@ -306,20 +823,6 @@ impl EscrowTestSetup {
         token_a_amount: u64,
         token_b_amount: u64,
     ) -> Result<()> {
-        let accounts = vec![
-            AccountMeta::new(self.escrow_pda, false),
-            AccountMeta::new(self.escrow_token_a_ata, false),
-            AccountMeta::new(self.maker.pubkey(), false),
-            AccountMeta::new(self.maker_token_b_ata, false),
-            AccountMeta::new(self.taker.pubkey(), true),
-            AccountMeta::new(self.taker_token_a_ata, false),
-            AccountMeta::new(self.taker_token_b_ata, false),
-            AccountMeta::new(self.program_id, false),
-            AccountMeta::new(self.program_id, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-        ];
-
         // Create instruction data for take escrow
         let mut ix_data = vec![0x02]; // Discriminator for take instruction
 
@@ -334,34 +837,169 @@ impl EscrowTestSetup {
             ix_data.extend_from_slice(&take_ix.pack());
         }
 
+        self.take_escrow_with_data(ix_data)
+    }
+
+    /// Take a partial amount from a partial escrow
+    pub fn take_partial_escrow(&mut self, token_a_amount: u64) -> Result<()> {
+        // Create instruction data for partial take
+        let mut ix_data = vec![0x02]; // Discriminator for take instruction
+
+        use escrow_suite::instructions::TakeEscrowIx;
+        let take_ix = TakeEscrowIx::new(
+            escrow_suite::states::EscrowType::Partial,
+            token_a_amount,
+            0, // token_b_amount will be calculated by the program
+        );
+        ix_data.extend_from_slice(&take_ix.pack());
+
+        self.take_escrow_with_data(ix_data)
+    }
+
+    /// Send a take instruction with raw instruction data (including the discriminator)
+    pub fn take_escrow_with_data(&mut self, ix_data: Vec<u8>) -> Result<()> {
+        self.take_escrow_with_extra_account(ix_data, self.program_id)
+    }
+
+    /// Take with a token B payment split across the taker's primary and a second token B account
+    pub fn take_escrow_split_payment(
+        &mut self,
+        take_ix: escrow_suite::instructions::TakeEscrowIx,
+        split_token_b_amount: u64,
+        taker_token_b_ata_2: Pubkey,
+    ) -> Result<()> {
+        let mut ix_data = vec![0x02]; // Discriminator for take instruction
+        ix_data.extend_from_slice(&take_ix.pack());
+        ix_data.extend_from_slice(&split_token_b_amount.to_le_bytes());
+        self.take_escrow_with_extra_account(ix_data, taker_token_b_ata_2)
+    }
+
+    /// Send a take instruction with `extra_account` in the first slot after the config account
+    fn take_escrow_with_extra_account(
+        &mut self,
+        ix_data: Vec<u8>,
+        extra_account: Pubkey,
+    ) -> Result<()> {
+        let instruction = self.take_instruction(ix_data, extra_account);
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[instruction], &[&taker])
+    }
+
+    /// Fill from the cheapest of `candidates`, each an (escrow, escrow token A account) of the maker
+    /// that charges no protocol fee
+    pub fn take_best(&mut self, candidates: &[(Pubkey, Pubkey)], ix: TakeBestIx) -> Result<()> {
+        let candidates: Vec<_> = candidates
+            .iter()
+            .map(|&(escrow, escrow_token_a_ata)| {
+                (escrow, escrow_token_a_ata, self.maker_token_b_ata)
+            })
+            .collect();
+        self.take_best_with_fee_collectors(&candidates, ix)
+    }
+
+    /// Fill from the cheapest of `candidates`, each an (escrow, escrow token A account, fee
+    /// collector) of the maker
+    pub fn take_best_with_fee_collectors(
+        &mut self,
+        candidates: &[(Pubkey, Pubkey, Pubkey)],
+        ix: TakeBestIx,
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new(self.taker.pubkey(), true),
+            AccountMeta::new(self.taker_token_a_ata, false),
+            AccountMeta::new(self.taker_token_b_ata, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        for (escrow, escrow_token_a_ata, fee_collector) in candidates {
+            accounts.extend([
+                AccountMeta::new(*escrow, false),
+                AccountMeta::new(*escrow_token_a_ata, false),
+                AccountMeta::new(self.maker.pubkey(), false),
+                AccountMeta::new(self.maker_token_b_ata, false),
+                AccountMeta::new(*fee_collector, false),
+            ]);
+        }
+
+        let mut ix_data = vec![0x15];
+        ix_data.extend_from_slice(&ix.pack());
         let instruction = Instruction {
             program_id: self.program_id,
             accounts,
             data: ix_data,
         };
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[instruction], &[&taker])
+    }
 
-        let msg = v0::Message::try_compile(
-            &self.taker.pubkey(),
-            &[instruction],
-            &[],
-            self.svm.latest_blockhash(),
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to compile message: {:?}", e))?;
+    /// Take each of `entries` in one batch take over `escrows`, each an (escrow, escrow token A
+    /// account) of the maker
+    pub fn take_batch(
+        &mut self,
+        escrows: &[(Pubkey, Pubkey)],
+        entries: &[TakeBatchEntry],
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new(self.taker.pubkey(), true),
+            AccountMeta::new(self.taker_token_a_ata, false),
+            AccountMeta::new(self.taker_token_b_ata, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        for (escrow, escrow_token_a_ata) in escrows {
+            // None of these charge a fee, so the maker's token B account fills the fee slot
+            accounts.extend([
+                AccountMeta::new(*escrow, false),
+                AccountMeta::new(*escrow_token_a_ata, false),
+                AccountMeta::new(self.maker.pubkey(), false),
+                AccountMeta::new(self.maker_token_b_ata, false),
+                AccountMeta::new(self.maker_token_b_ata, false),
+            ]);
+        }
 
-        let tx = VersionedTransaction::try_new(
-            VersionedMessage::V0(msg),
-            &[self.taker.insecure_clone()],
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create transaction: {:?}", e))?;
+        let mut ix_data = vec![0x09];
+        for entry in entries {
+            ix_data.extend_from_slice(&entry.pack());
+        }
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: ix_data,
+        };
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[instruction], &[&taker])
+    }
 
-        self.svm
-            .send_transaction(tx)
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
-        Ok(())
+    /// Take a wSOL-priced escrow paying with `wrap_lamports` of the taker's native SOL
+    pub fn take_escrow_with_sol(&mut self, wrap_lamports: u64) -> Result<()> {
+        let taker_wsol_ata =
+            get_associated_token_address(&self.taker.pubkey(), &spl_token::native_mint::ID);
+        let mut take = self.take_instruction(vec![0x17], self.program_id);
+        take.accounts.truncate(8);
+        take.accounts[6] = AccountMeta::new(taker_wsol_ata, false);
+        take.accounts.extend([
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ]);
+        take.data.extend_from_slice(&wrap_lamports.to_le_bytes());
+
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[take], &[&taker])
     }
 
-    /// Take a partial amount from a partial escrow
-    pub fn take_partial_escrow(&mut self, token_a_amount: u64) -> Result<()> {
+    /// Take the escrow at a limit price
+    pub fn take_limit(&mut self, ix: TakeLimitIx) -> Result<()> {
+        let mut ix_data = vec![0x18];
+        ix_data.extend_from_slice(&ix.pack());
+        let take = self.take_instruction(ix_data, self.program_id);
+        let taker = self.taker.insecure_clone();
+        self.send_instructions(&[take], &[&taker])
+    }
+
+    /// Build a take instruction with `extra_account` in the first slot after the config account
+    pub fn take_instruction(&self, ix_data: Vec<u8>, extra_account: Pubkey) -> Instruction {
         let accounts = vec![
             AccountMeta::new(self.escrow_pda, false),
             AccountMeta::new(self.escrow_token_a_ata, false),
@@ -370,23 +1008,91 @@ impl EscrowTestSetup {
             AccountMeta::new(self.taker.pubkey(), true),
             AccountMeta::new(self.taker_token_a_ata, false),
             AccountMeta::new(self.taker_token_b_ata, false),
-            AccountMeta::new(self.program_id, false),
+            AccountMeta::new_readonly(self.config_pda(), false),
+            AccountMeta::new(extra_account, false),
             AccountMeta::new(self.program_id, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token::ID, false),
         ];
 
-        // Create instruction data for partial take
-        let mut ix_data = vec![0x02]; // Discriminator for take instruction
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: ix_data,
+        }
+    }
 
-        use escrow_suite::instructions::TakeEscrowIx;
-        let take_ix = TakeEscrowIx::new(
-            escrow_suite::states::EscrowType::Partial,
-            token_a_amount,
-            0, // token_b_amount will be calculated by the program
-        );
-        ix_data.extend_from_slice(&take_ix.pack());
+    /// Send several instructions in one transaction; the first signer pays
+    pub fn send_instructions(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<()> {
+        self.send_instructions_with_logs(instructions, signers)
+            .map(|_| ())
+    }
+
+    /// Send `instructions` like `send_instructions`, returning the transaction logs
+    pub fn send_instructions_with_logs(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>> {
+        self.send_instructions_with_meta(instructions, signers)
+            .map(|meta| meta.logs)
+    }
+
+    /// Send `instructions` like `send_instructions`, returning the transaction metadata
+    pub fn send_instructions_with_meta(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata> {
+        let msg = v0::Message::try_compile(
+            &signers[0].pubkey(),
+            instructions,
+            &[],
+            self.svm.latest_blockhash(),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile message: {:?}", e))?;
+
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(msg), signers)
+            .map_err(|e| anyhow::anyhow!("Failed to create transaction: {:?}", e))?;
+
+        self.svm
+            .send_transaction(tx)
+            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))
+    }
+
+    /// Compute units `instructions` consume, whether or not the transaction succeeds
+    pub fn compute_units_consumed(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<u64> {
+        let msg = v0::Message::try_compile(
+            &signers[0].pubkey(),
+            instructions,
+            &[],
+            self.svm.latest_blockhash(),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile message: {:?}", e))?;
 
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(msg), signers)
+            .map_err(|e| anyhow::anyhow!("Failed to create transaction: {:?}", e))?;
+
+        Ok(match self.svm.send_transaction(tx) {
+            Ok(meta) => meta.compute_units_consumed,
+            Err(failed) => failed.meta.compute_units_consumed,
+        })
+    }
+
+    /// Simulate a read-only instruction signed by the taker and return its return data
+    pub fn simulate_return_data(
+        &self,
+        ix_data: Vec<u8>,
+        accounts: Vec<AccountMeta>,
+    ) -> Result<Vec<u8>> {
         let instruction = Instruction {
             program_id: self.program_id,
             accounts,
@@ -407,10 +1113,100 @@ impl EscrowTestSetup {
         )
         .map_err(|e| anyhow::anyhow!("Failed to create transaction: {:?}", e))?;
 
-        self.svm
-            .send_transaction(tx)
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
-        Ok(())
+        let result = self
+            .svm
+            .simulate_transaction(tx)
+            .map_err(|e| anyhow::anyhow!("Failed to simulate transaction: {:?}", e))?;
+        Ok(result.meta.return_data.data)
+    }
+
+    /// Query the escrow status byte via the status instruction
+    pub fn query_status(&self) -> Result<u8> {
+        let data = self.simulate_return_data(
+            vec![0x0C],
+            vec![AccountMeta::new_readonly(self.escrow_pda, false)],
+        )?;
+        data.first()
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Status instruction returned no data"))
+    }
+
+    /// Query (status, remaining token A) for each of `escrows` via the batch status instruction
+    pub fn query_batch_status(&self, escrows: &[Pubkey]) -> Result<Vec<(u8, u64)>> {
+        let accounts = escrows
+            .iter()
+            .map(|escrow| AccountMeta::new_readonly(*escrow, false))
+            .collect();
+        let data = self.simulate_return_data(vec![0x12], accounts)?;
+        Ok(data
+            .chunks_exact(1 + 8)
+            .map(|record| {
+                let remaining = u64::from_le_bytes(record[1..].try_into().unwrap());
+                (record[0], remaining)
+            })
+            .collect())
+    }
+
+    /// Query the `check_escrow` violation bitmask for `escrow` and its token A account
+    pub fn query_check_escrow(&self, escrow: Pubkey, escrow_token_a_ata: Pubkey) -> Result<u8> {
+        let data = self.simulate_return_data(
+            vec![0x19],
+            vec![
+                AccountMeta::new_readonly(escrow, false),
+                AccountMeta::new_readonly(escrow_token_a_ata, false),
+                AccountMeta::new_readonly(self.token_a_mint, false),
+            ],
+        )?;
+        data.first()
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Check escrow instruction returned no data"))
+    }
+
+    /// Query the token B the whole remaining lot costs now via the quote instruction
+    pub fn query_quote(&self) -> Result<u64> {
+        let data = self.simulate_return_data(
+            vec![0x08],
+            vec![AccountMeta::new_readonly(self.escrow_pda, false)],
+        )?;
+        let bytes = data
+            .try_into()
+            .map_err(|d| anyhow::anyhow!("Unexpected quote data: {:?}", d))?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Query the seconds left on a Dutch auction via the time remaining instruction
+    pub fn query_time_remaining(&self) -> Result<u64> {
+        let data = self.simulate_return_data(
+            vec![0x11],
+            vec![AccountMeta::new_readonly(self.escrow_pda, false)],
+        )?;
+        let bytes = data
+            .try_into()
+            .map_err(|d| anyhow::anyhow!("Unexpected time remaining data: {:?}", d))?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Read the partial fill history as (taker, token A amount, timestamp), most recent first
+    pub fn query_fill_history(&self) -> Result<Vec<(Pubkey, u64, u64)>> {
+        let data = self.simulate_return_data(
+            vec![0x0D],
+            vec![AccountMeta::new_readonly(self.escrow_pda, false)],
+        )?;
+        let (count, records) = data
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Fill history instruction returned no data"))?;
+
+        Ok(records
+            .chunks_exact(48)
+            .take(*count as usize)
+            .map(|record| {
+                (
+                    Pubkey::try_from(&record[0..32]).unwrap(),
+                    u64::from_le_bytes(record[32..40].try_into().unwrap()),
+                    u64::from_le_bytes(record[40..48].try_into().unwrap()),
+                )
+            })
+            .collect())
     }
 
     pub fn display_balances(&self) -> Result<()> {
@@ -443,7 +1239,11 @@ impl EscrowTestSetup {
 
     pub fn get_balance(&self, user: &Pubkey, mint: &Pubkey) -> u64 {
         let ata = get_associated_token_address(user, mint);
-        if let Some(account) = self.svm.get_account(&ata) {
+        self.get_token_account_balance(&ata)
+    }
+
+    pub fn get_token_account_balance(&self, token_account: &Pubkey) -> u64 {
+        if let Some(account) = self.svm.get_account(token_account) {
             if account.data.len() >= 72 {
                 u64::from_le_bytes(account.data[64..72].try_into().unwrap())
             } else {
@@ -454,6 +1254,20 @@ impl EscrowTestSetup {
         }
     }
 
+    /// Mark a token account as frozen by rewriting its SPL account state
+    pub fn freeze_token_account(&mut self, token_account: &Pubkey) -> Result<()> {
+        let mut account = self
+            .svm
+            .get_account(token_account)
+            .ok_or_else(|| anyhow::anyhow!("Token account not found"))?;
+        // Account state lives at byte 108 of the SPL token account layout (2 = Frozen)
+        account.data[108] = 2;
+        self.svm
+            .set_account(*token_account, account)
+            .map_err(|e| anyhow::anyhow!("Failed to set account: {:?}", e))?;
+        Ok(())
+    }
+
     pub fn get_maker_token_a_balance(&self) -> u64 {
         self.get_balance(&self.maker.pubkey(), &self.token_a_mint)
     }
@@ -470,6 +1284,16 @@ impl EscrowTestSetup {
         self.get_balance(&self.taker.pubkey(), &self.token_b_mint)
     }
 
+    /// Decode the escrow account at `escrow`
+    pub fn get_escrow(&self, escrow: &Pubkey) -> Result<Escrow> {
+        let account = self
+            .svm
+            .get_account(escrow)
+            .ok_or_else(|| anyhow::anyhow!("Escrow account not found"))?;
+        Escrow::unpack(&account.data)
+            .map_err(|e| anyhow::anyhow!("Failed to unpack escrow: {:?}", e))
+    }
+
     pub fn get_escrow_token_a_balance(&self) -> u64 {
         self.get_balance(&self.escrow_pda, &self.token_a_mint)
     }
@@ -773,6 +1597,7 @@ impl EscrowTestSetup {
                 EscrowType::Partial => "Partial",
                 EscrowType::Oracle => "Oracle",
                 EscrowType::DutchAuction => "Dutch Auction",
+                EscrowType::English => "English Auction",
             }
         );
         println!("Token A Amount: {}", token_a_amount);
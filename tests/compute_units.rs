@@ -0,0 +1,73 @@
+use anyhow::Result;
+use escrow_suite::{instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::pubkey::Pubkey;
+
+mod common;
+pub use common::*;
+
+// ==================== COMPUTE UNIT BENCHMARKS ====================
+
+/// Most compute units a Simple make or take may consume
+const MAKE_COMPUTE_UNIT_LIMIT: u64 = 30_000;
+const TAKE_COMPUTE_UNIT_LIMIT: u64 = 30_000;
+
+/// Most compute units the escrow address check in a take may add. Hashing the stored seeds
+/// with `sol_sha256` costs ~150 CUs; the `create_program_address` re-derivation it replaced
+/// cost 1,500 CUs before logging the derived address.
+const ADDRESS_CHECK_COMPUTE_UNIT_LIMIT: u64 = 1_000;
+
+/// Measures the compute units make and take consume and checks them against fixed budgets
+#[test]
+fn test_make_and_take_compute_units() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Benchmarking Make And Take Compute Units ===");
+
+    let maker = setup.maker.insecure_clone();
+    let make = setup.make_instruction(MakeEscrowIx::new(
+        EscrowType::Simple,
+        1000,
+        2000,
+        setup.bump,
+        setup.seed,
+    ));
+    let make_meta = setup.send_instructions_with_meta(&[make], &[&maker])?;
+    println!("make: {} CUs", make_meta.compute_units_consumed);
+
+    let taker = setup.taker.insecure_clone();
+    let take = setup.take_instruction(vec![0x02], setup.program_id);
+    let take_meta = setup.send_instructions_with_meta(&[take], &[&taker])?;
+    println!("take: {} CUs", take_meta.compute_units_consumed);
+
+    assert!(make_meta.compute_units_consumed <= MAKE_COMPUTE_UNIT_LIMIT);
+    assert!(take_meta.compute_units_consumed <= TAKE_COMPUTE_UNIT_LIMIT);
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    println!("✅ Make and take compute units benchmark passed");
+    Ok(())
+}
+
+/// Measures the escrow address check on its own: a take with the wrong maker fails just before
+/// it and a take with the wrong vault just after it, so the difference is what the check costs
+#[test]
+fn test_escrow_address_check_compute_units() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let taker = setup.taker.insecure_clone();
+
+    let mut before_check = setup.take_instruction(vec![0x02], setup.program_id);
+    before_check.accounts[2].pubkey = Pubkey::new_unique();
+    let before = setup.compute_units_consumed(&[before_check], &[&taker])?;
+
+    let mut after_check = setup.take_instruction(vec![0x02], setup.program_id);
+    after_check.accounts[1].pubkey = setup.taker_token_a_ata;
+    let after = setup.compute_units_consumed(&[after_check], &[&taker])?;
+
+    println!("escrow address check: {} CUs", after - before);
+    assert!(after > before);
+    assert!(after - before <= ADDRESS_CHECK_COMPUTE_UNIT_LIMIT);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::SetConfigIx, states::EscrowType};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_token_b_mint_allowlist() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 2000;
+    let token_b_amount = 4000;
+
+    println!("=== Testing Token B Mint Allowlist ===");
+
+    // Only an unrelated payment mint is approved
+    let approved_mint = Pubkey::new_unique();
+    setup.set_config(SetConfigIx::with_allowlist(&[approved_mint.to_bytes()]).unwrap())?;
+
+    let result = setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount);
+    assert_escrow_error(result, EscrowErrorCode::MintNotAllowed)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    // Approving the escrow's token B mint lets the make through
+    let token_b_mint = setup.token_b_mint;
+    setup.set_config(
+        SetConfigIx::with_allowlist(&[approved_mint.to_bytes(), token_b_mint.to_bytes()]).unwrap(),
+    )?;
+
+    setup.svm.expire_blockhash();
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    println!("✅ Token B mint allowlist test passed");
+    Ok(())
+}
+
+#[test]
+fn test_takes_paused_blocks_only_takes() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Takes Paused ===");
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    setup.set_config(SetConfigIx {
+        takes_paused: true,
+        ..SetConfigIx::default()
+    })?;
+
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::ProgramPaused)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    // Makers can still cancel and create escrows while takes are paused
+    setup.cancel_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    setup.use_seed(7u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Unpausing lets the take through
+    setup.set_config(SetConfigIx::default())?;
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    println!("✅ Takes paused test passed");
+    Ok(())
+}
+
+#[test]
+fn test_max_auction_duration() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Max Auction Duration ===");
+
+    setup.set_config(SetConfigIx {
+        max_auction_duration: 3600,
+        ..SetConfigIx::default()
+    })?;
+
+    let result = setup.create_dutch_auction_escrow(1000, 2000, 1000, 3601);
+    assert_escrow_error(result, EscrowErrorCode::InvalidDuration)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    setup.create_dutch_auction_escrow(1000, 2000, 1000, 3600)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    println!("✅ Max auction duration test passed");
+    Ok(())
+}
+
+#[test]
+fn test_only_upgrade_authority_creates_config() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Config Creation Authority ===");
+
+    // The maker is the upgrade authority, so the taker cannot claim the config first
+    let maker = setup.maker.pubkey();
+    setup.set_upgrade_authority(&maker)?;
+
+    let taker = setup.taker.insecure_clone();
+    let instruction = setup.set_config_instruction(&taker.pubkey(), SetConfigIx::default());
+    let result = setup.send_instructions(&[instruction], &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::Unauthorized)?;
+    assert!(setup.svm.get_account(&setup.config_pda()).is_none());
+
+    // The upgrade authority creates it and becomes the admin
+    setup.set_config(SetConfigIx::default())?;
+    let config = setup.svm.get_account(&setup.config_pda()).unwrap();
+    assert_eq!(&config.data[..32], maker.as_ref());
+
+    println!("✅ Config creation authority test passed");
+    Ok(())
+}
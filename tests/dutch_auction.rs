@@ -1,4 +1,13 @@
 use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{MakeEscrowIx, TakeEscrowIx, MAX_AUCTION_DURATION},
+    states::EscrowType,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    sysvar,
+};
 
 mod common;
 pub use common::*;
@@ -456,3 +465,363 @@ fn test_dutch_auction_multiple_auctions() -> Result<()> {
     println!("✅ Multiple Dutch auctions test passed");
     Ok(())
 }
+
+#[test]
+fn test_dutch_auction_partial_fills_at_decaying_prices() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Dutch Auction Partial Fills ===");
+
+    let duration = 1000;
+    let start_price = 10000;
+    let end_price = 5000;
+    let token_a_amount = 2000;
+    let start_time = 1_000_000;
+
+    setup.set_time(start_time)?;
+    setup.create_dutch_auction_escrow(token_a_amount, start_price, end_price, duration)?;
+
+    // First tranche at the start: half the lot at half the start price
+    let first_tranche = 1000;
+    let first_cost = start_price * first_tranche / token_a_amount;
+    setup.take_escrow_with_amounts(first_tranche, first_cost)?;
+
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + first_cost);
+    assert_eq!(
+        setup.get_escrow_token_a_balance(),
+        token_a_amount - first_tranche
+    );
+
+    // Second tranche halfway through: priced on the original decay schedule
+    setup.advance_time(duration as i64 / 2)?;
+    let lot_price = setup.calculate_expected_dutch_price(
+        start_price,
+        end_price,
+        start_time as u64,
+        start_time as u64 + duration,
+        start_time as u64 + duration / 2,
+    );
+    let second_tranche = token_a_amount - first_tranche;
+    let second_cost = lot_price * second_tranche / token_a_amount;
+    println!(
+        "Lot price at midpoint: {}, second tranche cost: {}",
+        lot_price, second_cost
+    );
+    setup.take_escrow_with_amounts(second_tranche, second_cost)?;
+
+    assert_eq!(
+        setup.get_maker_token_b_balance(),
+        10000 + first_cost + second_cost
+    );
+    assert_eq!(
+        setup.get_taker_token_b_balance(),
+        10000 - first_cost - second_cost
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + token_a_amount);
+    assert_eq!(setup.get_escrow_token_a_balance(), 0);
+
+    println!("✅ Dutch auction partial fills test passed");
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_fill_or_kill() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Dutch Auction Fill-or-Kill ===");
+
+    let duration = 1000;
+    let start_price = 5000;
+    let end_price = 1000;
+    let token_a_amount = 1000;
+
+    setup.set_time(1_000_000)?;
+    setup.create_dutch_auction_escrow(token_a_amount, start_price, end_price, duration)?;
+
+    // Drain most of the lot at the start price of 5 token B per unit
+    setup.take_escrow_with_amounts(600, 3000)?;
+    let remaining = token_a_amount - 600;
+    assert_eq!(setup.get_escrow_token_a_balance(), remaining);
+
+    // A fill-or-kill take for more than remains is rejected
+    let mut take_ix = TakeEscrowIx::new(EscrowType::DutchAuction, 500, 2500);
+    take_ix.fill_or_kill = true;
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&take_ix.pack_extended());
+    let result = setup.take_escrow_with_data(ix_data);
+    assert_escrow_error(result, EscrowErrorCode::ExceedsEscrowBalance)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), remaining);
+
+    // The same take without fill-or-kill fills what's available
+    setup.take_escrow_with_amounts(500, 2500)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 0);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + token_a_amount);
+    assert_eq!(
+        setup.get_maker_token_b_balance(),
+        10000 + 3000 + remaining * 5,
+        "Only the filled amount should be charged"
+    );
+
+    println!("✅ Dutch auction fill-or-kill test passed");
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_quote_matches_decayed_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let start_time: u64 = 1_000_000;
+    let duration = 1000;
+    setup.set_time(start_time as i64)?;
+    setup.create_dutch_auction_escrow(1000, 5000, 1000, duration)?;
+
+    for elapsed in [0, 250, 999, 1500] {
+        setup.set_time((start_time + elapsed) as i64)?;
+        let expected = setup.calculate_expected_dutch_price(
+            5000,
+            1000,
+            start_time,
+            start_time + duration,
+            start_time + elapsed,
+        );
+        assert_eq!(
+            setup.query_quote()?,
+            expected,
+            "{}s into the auction",
+            elapsed
+        );
+    }
+
+    // Quoting moves no tokens
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_quote_after_partial_fill_covers_remaining_lot() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let start_time: u64 = 1_000_000;
+    let duration = 1000;
+    setup.set_time(start_time as i64)?;
+    setup.create_dutch_auction_escrow(2000, 10000, 5000, duration)?;
+
+    setup.take_escrow_with_amounts(1000, 5000)?;
+
+    // The quote prices only what's left, on the original decay schedule
+    setup.set_time((start_time + duration / 2) as i64)?;
+    let lot_price = setup.calculate_expected_dutch_price(
+        10000,
+        5000,
+        start_time,
+        start_time + duration,
+        start_time + duration / 2,
+    );
+    let quoted = setup.query_quote()?;
+    assert_eq!(quoted, lot_price * 1000 / 2000);
+
+    setup.take_escrow_with_amounts(1000, quoted)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_time_remaining() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Dutch Auction Time Remaining ===");
+
+    let duration = 1000;
+    setup.set_time(1_000_000)?;
+    setup.create_dutch_auction_escrow(1000, 5000, 1000, duration)?;
+
+    assert_eq!(setup.query_time_remaining()?, duration);
+
+    setup.advance_time(300)?;
+    assert_eq!(setup.query_time_remaining()?, duration - 300);
+
+    setup.advance_time(650)?;
+    assert_eq!(setup.query_time_remaining()?, duration - 950);
+
+    // Past the end the countdown stays at zero
+    setup.advance_time(500)?;
+    assert_eq!(setup.query_time_remaining()?, 0);
+
+    println!("✅ Dutch auction time remaining test passed");
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_rejects_zero_start_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Dutch Auction Zero Start Price ===");
+
+    let result = setup.create_dutch_auction_escrow(2000, 0, 0, 3600);
+    assert_escrow_error(result, EscrowErrorCode::ZeroAmount)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    println!("✅ Dutch auction zero start price test passed");
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_rejects_end_price_above_start_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let result = setup.create_dutch_auction_escrow(2000, 4000, 8000, 3600);
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuctionParams)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    // A flat auction that never decays is allowed
+    setup.create_dutch_auction_escrow(2000, 4000, 4000, 3600)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_rejects_overflowing_duration() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.set_time(1_000_000)?;
+    let result = setup.create_dutch_auction_escrow(2000, 8000, 4000, u64::MAX);
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuctionParams)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    setup.create_dutch_auction_escrow(2000, 8000, 4000, MAX_AUCTION_DURATION)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_take_requires_body() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_dutch_auction_escrow(2000, 8000, 4000, 3600)?;
+
+    let result = setup.take_escrow();
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.take_escrow_with_amounts(2000, 8000)?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_reprice_mid_auction() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Dutch Auction Reprice ===");
+
+    let start_time = 1_000_000;
+    setup.set_time(start_time)?;
+    setup.create_dutch_auction_escrow(1000, 10000, 5000, 1000)?;
+
+    // Halfway through the price is 7500; the maker restarts the decay at 9000
+    setup.advance_time(500)?;
+    let result = setup.reprice_dutch(4000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidPriceRange)?;
+    setup.reprice_dutch(9000)?;
+
+    // Halfway through the remaining 500 seconds the price is midway from 9000 to 5000
+    setup.advance_time(250)?;
+    let result = setup.take_escrow_with_amounts(1000, 6999);
+    assert_escrow_error(result, EscrowErrorCode::DutchUnderpayment)?;
+    setup.take_escrow_with_amounts(1000, 7000)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 7000);
+
+    println!("✅ Dutch auction reprice test passed");
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_take_charges_current_price_below_cap() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let start_time = 1_000_000;
+    setup.set_time(start_time)?;
+    setup.create_dutch_auction_escrow(1000, 10000, 5000, 1000)?;
+
+    // The taker quoted the start price, but the auction has decayed to 7500 by the time
+    // the take lands
+    setup.advance_time(500)?;
+    setup.take_escrow_with_amounts(1000, 10000)?;
+
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 7500);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 7500);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_expire_at_floor_rejects_takes_after_end_time() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let start_time = 1_000_000;
+    setup.set_time(start_time)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expire_at_floor: true,
+        ..MakeEscrowIx::new_dutch_auction(
+            1000,
+            10000,
+            5000,
+            start_time as u64,
+            start_time as u64 + 1000,
+            setup.bump,
+            setup.seed,
+        )
+    })?;
+
+    // Once the auction reaches its floor the maker would rather cancel than sell at it
+    setup.advance_time(1000)?;
+    let result = setup.take_escrow_with_amounts(1000, 5000);
+    assert_escrow_error(result, EscrowErrorCode::EscrowExpired)?;
+
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_dutch_auction_take_with_explicit_clock_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.set_time(1_000_000)?;
+
+    // Both the make and the take read the time from the passed clock sysvar
+    let mut make = setup.make_instruction(MakeEscrowIx::new_dutch_auction(
+        2000, 10000, 5000, 0, 3600, setup.bump, setup.seed,
+    ));
+    make.accounts
+        .push(AccountMeta::new_readonly(sysvar::clock::ID, false));
+    let maker = setup.maker.insecure_clone();
+    setup.send_instructions(&[make], &[&maker])?;
+
+    let escrow = setup.get_escrow(&setup.escrow_pda)?;
+    assert_eq!((escrow.start_time, escrow.end_time), (1_000_000, 1_003_600));
+
+    // Halfway through, the lot costs 7500 token B
+    setup.advance_time(1800)?;
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::DutchAuction, 2000, 10000).pack());
+    let take = setup.take_instruction(ix_data, sysvar::clock::ID);
+    let taker = setup.taker.insecure_clone();
+    setup.send_instructions(&[take], &[&taker])?;
+
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 7500);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 7500);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 2000);
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+mod common;
+pub use common::*;
+
+/// Create a funded bidder holding 10000 token B, returning it with its token B account
+fn new_bidder(setup: &mut EscrowTestSetup) -> Result<(Keypair, Pubkey)> {
+    let bidder = Keypair::new();
+    setup
+        .svm
+        .airdrop(&bidder.pubkey(), 10_000_000)
+        .map_err(|e| anyhow::anyhow!("Failed to airdrop: {:?}", e))?;
+    let token_b_mint = setup.token_b_mint;
+    let token_b_ata = setup_ata(&mut setup.svm, &token_b_mint, &bidder.pubkey(), &bidder)?;
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &setup.maker,
+        &token_b_ata,
+        10000,
+    )?;
+    Ok((bidder, token_b_ata))
+}
+
+/// Open an English auction of 1000 token A with a reserve price of 1000 token B
+fn create_english_auction(setup: &mut EscrowTestSetup, duration: u64) -> Result<Pubkey> {
+    let escrow_token_b_ata = setup.setup_escrow_token_b_ata()?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        duration,
+        ..MakeEscrowIx::new(EscrowType::English, 1000, 1000, setup.bump, setup.seed)
+    })?;
+    Ok(escrow_token_b_ata)
+}
+
+#[test]
+fn test_english_auction_outbid_refunds_previous_bidder() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing English Auction Bidding ===");
+
+    let escrow_token_b_ata = create_english_auction(&mut setup, 3600)?;
+    let taker = setup.taker.insecure_clone();
+    let taker_token_b_ata = setup.taker_token_b_ata;
+
+    // Bids must meet the reserve price
+    let result = setup.place_bid(&taker, taker_token_b_ata, 999);
+    assert_escrow_error(result, EscrowErrorCode::BidTooLow)?;
+
+    setup.place_bid(&taker, taker_token_b_ata, 1500)?;
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 1500);
+    assert_eq!(setup.get_token_account_balance(&escrow_token_b_ata), 1500);
+
+    // Matching the highest bid isn't enough
+    let (bidder, bidder_token_b_ata) = new_bidder(&mut setup)?;
+    let result = setup.place_bid(&bidder, taker_token_b_ata, 1500);
+    assert_escrow_error(result, EscrowErrorCode::BidTooLow)?;
+
+    // The refund must go to the outbid bidder
+    let result = setup.place_bid(&bidder, bidder_token_b_ata, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenOwner)?;
+
+    setup.place_bid(&bidder, taker_token_b_ata, 2000)?;
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+    assert_eq!(setup.get_token_account_balance(&bidder_token_b_ata), 8000);
+    assert_eq!(setup.get_token_account_balance(&escrow_token_b_ata), 2000);
+
+    let escrow = setup.get_escrow(&setup.escrow_pda)?;
+    assert_eq!(escrow.highest_bid, 2000);
+    assert_eq!(escrow.highest_bidder, bidder.pubkey().to_bytes());
+
+    // Bids hold the maker to the auction
+    let result = setup.cancel_escrow();
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+
+    println!("✅ English auction bidding test passed");
+    Ok(())
+}
+
+#[test]
+fn test_english_auction_settles_to_highest_bidder() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing English Auction Settlement ===");
+
+    let escrow_token_b_ata = create_english_auction(&mut setup, 3600)?;
+    let taker = setup.taker.insecure_clone();
+    let taker_token_a_ata = setup.taker_token_a_ata;
+    let taker_token_b_ata = setup.taker_token_b_ata;
+
+    setup.place_bid(&taker, taker_token_b_ata, 1500)?;
+
+    // English auctions can't be taken, and settle only once bidding closes
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowType)?;
+    let result = setup.settle_auction(taker_token_a_ata);
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+
+    setup.advance_time(3600)?;
+    let result = setup.place_bid(&taker, taker_token_b_ata, 2000);
+    assert_escrow_error(result, EscrowErrorCode::EscrowExpired)?;
+
+    let maker_lamports = setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0);
+    let escrow_rent = [
+        setup.escrow_pda,
+        setup.escrow_token_a_ata,
+        escrow_token_b_ata,
+    ]
+    .iter()
+    .map(|account| setup.svm.get_balance(account).unwrap_or(0))
+    .sum::<u64>();
+
+    setup.settle_auction(taker_token_a_ata)?;
+
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 1500);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 1500);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert!(setup.svm.get_account(&escrow_token_b_ata).is_none());
+    assert_eq!(
+        setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0),
+        maker_lamports + escrow_rent
+    );
+
+    println!("✅ English auction settlement test passed");
+    Ok(())
+}
+
+#[test]
+fn test_english_auction_settle_rejects_third_party_token_b_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    create_english_auction(&mut setup, 3600)?;
+    let taker = setup.taker.insecure_clone();
+    let taker_token_a_ata = setup.taker_token_a_ata;
+    let taker_token_b_ata = setup.taker_token_b_ata;
+    setup.place_bid(&taker, taker_token_b_ata, 1500)?;
+    setup.advance_time(3600)?;
+
+    // A third party settling can't redirect the winning bid to itself
+    let (_, outsider_token_b_ata) = new_bidder(&mut setup)?;
+    let maker_token_b_ata = setup.maker_token_b_ata;
+    setup.maker_token_b_ata = outsider_token_b_ata;
+    let result = setup.settle_auction(taker_token_a_ata);
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenOwner)?;
+    assert_eq!(
+        setup.get_token_account_balance(&outsider_token_b_ata),
+        10000
+    );
+
+    setup.maker_token_b_ata = maker_token_b_ata;
+    setup.settle_auction(taker_token_a_ata)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 1500);
+
+    Ok(())
+}
+
+#[test]
+fn test_english_auction_without_bids_is_refunded() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // A bidding window is required
+    setup.setup_escrow_token_b_ata()?;
+    let result = setup.create_escrow_with_ix(MakeEscrowIx {
+        duration: 0,
+        ..MakeEscrowIx::new(EscrowType::English, 1000, 1000, setup.bump, setup.seed)
+    });
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuctionParams)?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        duration: 3600,
+        ..MakeEscrowIx::new(EscrowType::English, 1000, 1000, setup.bump, setup.seed)
+    })?;
+    setup.advance_time(3600)?;
+
+    let taker_token_a_ata = setup.taker_token_a_ata;
+    let result = setup.settle_auction(taker_token_a_ata);
+    assert_escrow_error(result, EscrowErrorCode::NoBids)?;
+
+    setup.finalize_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
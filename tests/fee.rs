@@ -0,0 +1,241 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{MakeEscrowIx, SetConfigIx, TakeEscrowIx},
+    states::EscrowType,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+mod common;
+pub use common::*;
+
+/// Create `fee_recipient`'s token B account
+fn setup_fee_collector(setup: &mut EscrowTestSetup, fee_recipient: &Pubkey) -> Result<Pubkey> {
+    let token_b_mint = setup.token_b_mint;
+    setup_ata(&mut setup.svm, &token_b_mint, fee_recipient, &setup.maker)
+}
+
+fn create_escrow_with_fee(
+    setup: &mut EscrowTestSetup,
+    escrow_type: EscrowType,
+    token_b_amount: u64,
+    fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        fee_bps,
+        fee_recipient: fee_recipient.to_bytes(),
+        ..MakeEscrowIx::new(escrow_type, 1000, token_b_amount, setup.bump, setup.seed)
+    })
+}
+
+/// Take with `fee_account` in the first slot after the config account
+fn take_with_fee_account(
+    setup: &mut EscrowTestSetup,
+    ix_data: Vec<u8>,
+    fee_account: Pubkey,
+) -> Result<()> {
+    let take = setup.take_instruction(ix_data, fee_account);
+    let taker = setup.taker.insecure_clone();
+    setup.send_instructions(&[take], &[&taker])
+}
+
+/// The fee vault authority for token B and a fee vault it owns
+fn setup_fee_vault(setup: &mut EscrowTestSetup) -> Result<(Pubkey, Pubkey)> {
+    let (authority, _) = Pubkey::find_program_address(
+        &[b"FeeVault", setup.token_b_mint.as_ref()],
+        &setup.program_id,
+    );
+    let fee_vault = setup_fee_collector(setup, &authority)?;
+    Ok((authority, fee_vault))
+}
+
+/// Withdraw `fee_vault` into `destination`, signed by `admin`
+fn withdraw_fees(
+    setup: &mut EscrowTestSetup,
+    admin: &Keypair,
+    fee_vault_authority: Pubkey,
+    fee_vault: Pubkey,
+    destination: Pubkey,
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: setup.program_id,
+        accounts: vec![
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new_readonly(setup.config_pda(), false),
+            AccountMeta::new_readonly(fee_vault_authority, false),
+            AccountMeta::new(fee_vault, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: vec![0x1D],
+    };
+    setup.send_instructions(&[instruction], &[admin])
+}
+
+#[test]
+fn test_simple_escrow_take_pays_protocol_fee() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Protocol Fee on Simple Take ===");
+
+    // A treasury of the maker's choosing, e.g. a DAO running the offer
+    let treasury = Pubkey::new_unique();
+    let fee_collector = setup_fee_collector(&mut setup, &treasury)?;
+    create_escrow_with_fee(&mut setup, EscrowType::Simple, 2000, 250, treasury)?;
+    assert_eq!(
+        setup.get_escrow(&setup.escrow_pda)?.fee_recipient,
+        treasury.to_bytes()
+    );
+
+    take_with_fee_account(&mut setup, vec![0x02], fee_collector)?;
+
+    // 2.5% of 2000 goes to the treasury's token B account, the rest to the maker
+    assert_eq!(setup.get_token_account_balance(&fee_collector), 50);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 1950);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 2000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    println!("✅ Simple take protocol fee test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_take_pays_protocol_fee() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let treasury = Pubkey::new_unique();
+    let fee_collector = setup_fee_collector(&mut setup, &treasury)?;
+    create_escrow_with_fee(&mut setup, EscrowType::Partial, 4000, 100, treasury)?;
+
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Partial, 250, 0).pack());
+    take_with_fee_account(&mut setup, ix_data, fee_collector)?;
+
+    // 1% of the 1000 token B charged for a quarter of the lot
+    assert_eq!(setup.get_token_account_balance(&fee_collector), 10);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 990);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 1000);
+    assert_eq!(setup.get_escrow(&setup.escrow_pda)?.token_b_collected, 990);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_rejects_fee_collector_of_another_owner() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let treasury = Pubkey::new_unique();
+    setup_fee_collector(&mut setup, &treasury)?;
+    create_escrow_with_fee(&mut setup, EscrowType::Simple, 2000, 250, treasury)?;
+
+    // A token B account the taker controls instead of the fee recipient's
+    let token_b_mint = setup.token_b_mint;
+    let taker = setup.taker.pubkey();
+    let impostor = setup_token_account(&mut setup.svm, &token_b_mint, &taker, &setup.maker)?;
+
+    let result = take_with_fee_account(&mut setup, vec![0x02], impostor);
+    assert_escrow_error(result, EscrowErrorCode::InvalidFeeAccount)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_rejects_fee_paid_back_to_the_taker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The fee recipient is the taker, so the fee transfer never leaves their token B account
+    let taker = setup.taker.pubkey();
+    create_escrow_with_fee(&mut setup, EscrowType::Simple, 2000, 250, taker)?;
+
+    let taker_token_b_ata = setup.taker_token_b_ata;
+    let result = take_with_fee_account(&mut setup, vec![0x02], taker_token_b_ata);
+    assert_escrow_error(result, EscrowErrorCode::TokenBChargeMismatch)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_without_fee_recipient_charges_no_fee() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    create_escrow_with_fee(&mut setup, EscrowType::Simple, 2000, 250, Pubkey::default())?;
+    setup.take_escrow()?;
+
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 2000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_fee_on_auctions() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let result = setup.create_escrow_with_ix(MakeEscrowIx {
+        fee_bps: 100,
+        ..MakeEscrowIx::new_dutch_auction(1000, 2000, 1000, 0, 3600, setup.bump, setup.seed)
+    });
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowType)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_fee_vault_accumulates_fees_for_the_admin_to_withdraw() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The maker becomes the config admin
+    setup.set_config(SetConfigIx::default())?;
+    let (authority, fee_vault) = setup_fee_vault(&mut setup)?;
+    create_escrow_with_fee(&mut setup, EscrowType::Partial, 4000, 100, authority)?;
+
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Partial, 250, 0).pack());
+    for _ in 0..3 {
+        take_with_fee_account(&mut setup, ix_data.clone(), fee_vault)?;
+    }
+    // 1% of the 1000 token B each quarter of the lot costs
+    assert_eq!(setup.get_token_account_balance(&fee_vault), 30);
+
+    let admin = setup.maker.insecure_clone();
+    let token_b_mint = setup.token_b_mint;
+    let treasury = setup_token_account(&mut setup.svm, &token_b_mint, &admin.pubkey(), &admin)?;
+    withdraw_fees(&mut setup, &admin, authority, fee_vault, treasury)?;
+
+    assert_eq!(setup.get_token_account_balance(&treasury), 30);
+    assert_eq!(setup.get_token_account_balance(&fee_vault), 0);
+
+    // Nothing left to withdraw
+    let result = withdraw_fees(&mut setup, &admin, authority, fee_vault, treasury);
+    assert_escrow_error(result, EscrowErrorCode::InsufficientFunds)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_withdraw_fees_requires_the_config_admin() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.set_config(SetConfigIx::default())?;
+    let (authority, fee_vault) = setup_fee_vault(&mut setup)?;
+    create_escrow_with_fee(&mut setup, EscrowType::Simple, 2000, 250, authority)?;
+    take_with_fee_account(&mut setup, vec![0x02], fee_vault)?;
+
+    let taker = setup.taker.insecure_clone();
+    let taker_token_b_ata = setup.taker_token_b_ata;
+    let result = withdraw_fees(&mut setup, &taker, authority, fee_vault, taker_token_b_ata);
+    assert_escrow_error(result, EscrowErrorCode::Unauthorized)?;
+    assert_eq!(setup.get_token_account_balance(&fee_vault), 50);
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::MakeEscrowIx,
+    states::{Escrow, EscrowType},
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+mod common;
+pub use common::*;
+
+fn has_close_event(setup: &EscrowTestSetup, logs: &[String], reason: &str) -> bool {
+    let escrow_id = Escrow::escrow_id(&setup.maker.pubkey().to_bytes(), &setup.seed);
+    let event = format!("ESCROW_CLOSED id={} reason={}", escrow_id, reason);
+    logs.iter().any(|log| log.contains(&event))
+}
+
+#[test]
+fn test_finalize_expired_auction_refunds_maker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Finalize Expired Auction ===");
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expire_at_floor: true,
+        ..MakeEscrowIx::new_dutch_auction(2000, 4000, 2000, 0, 600, setup.bump, setup.seed)
+    })?;
+
+    // A live auction can't be finalized
+    let result = setup.finalize_escrow().map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+
+    setup.advance_time(600)?;
+    setup.svm.expire_blockhash();
+    let logs = setup.finalize_escrow()?;
+
+    assert!(
+        has_close_event(&setup, &logs, "Expired"),
+        "logs: {:?}",
+        logs
+    );
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    println!("✅ Finalize expired auction test passed");
+    Ok(())
+}
+
+#[test]
+fn test_finalize_rejects_auction_still_selling_at_floor() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Past end_time a default auction still sells at its end price
+    setup.create_dutch_auction_escrow(2000, 4000, 2000, 600)?;
+    setup.advance_time(1200)?;
+
+    let result = setup.finalize_escrow().map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.take_escrow_with_amounts(2000, 2000)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_finalize_filled_escrow_closes_it() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Finalize Filled Escrow ===");
+
+    // Stray tokens in the vault keep the drained escrow open after the take
+    setup.create_escrow(EscrowType::Simple, 2000, 3000)?;
+    let (token_a_mint, escrow_token_a_ata) = (setup.token_a_mint, setup.escrow_token_a_ata);
+    mint_to(
+        &mut setup.svm,
+        &token_a_mint,
+        &setup.maker,
+        &escrow_token_a_ata,
+        5,
+    )?;
+    setup.take_escrow()?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_some());
+    let maker_lamports = setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0);
+
+    let logs = setup.finalize_escrow()?;
+
+    assert!(has_close_event(&setup, &logs, "Filled"), "logs: {:?}", logs);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert!(
+        setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0) > maker_lamports,
+        "The maker should get the escrow rent back"
+    );
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 2000 + 5);
+
+    println!("✅ Finalize filled escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_third_party_reclaims_expired_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Reclaim Expired Escrow ===");
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expiry: now as u64 + 600,
+        ..MakeEscrowIx::new(EscrowType::Simple, 2000, 3000, setup.bump, setup.seed)
+    })?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 2000);
+
+    let cranker = Keypair::new();
+    setup
+        .svm
+        .airdrop(&cranker.pubkey(), 1_000_000_000)
+        .map_err(|e| anyhow::anyhow!("Failed to airdrop: {:?}", e))?;
+
+    // Not reclaimable until the expiry has passed
+    setup.advance_time(600)?;
+    let result = setup.reclaim_expired(&cranker).map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+
+    setup.advance_time(1)?;
+    setup.svm.expire_blockhash();
+    let maker_lamports = setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0);
+    let logs = setup.reclaim_expired(&cranker)?;
+
+    assert!(
+        has_close_event(&setup, &logs, "Expired"),
+        "logs: {:?}",
+        logs
+    );
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert!(setup.svm.get_account(&setup.escrow_token_a_ata).is_none());
+    assert!(
+        setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0) > maker_lamports,
+        "The maker should get the escrow rent back"
+    );
+
+    println!("✅ Reclaim expired escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_reclaim_rejects_escrow_without_expiry() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Reclaim Without Expiry ===");
+
+    setup.create_escrow(EscrowType::Simple, 2000, 3000)?;
+    setup.advance_time(365 * 24 * 60 * 60)?;
+
+    let taker = setup.taker.insecure_clone();
+    let result = setup.reclaim_expired(&taker).map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::EscrowStillActive)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    println!("✅ Reclaim without expiry test passed");
+    Ok(())
+}
+
+#[test]
+fn test_reclaim_respects_proceeds_floor() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The offer expires an hour before its proceeds floor stops locking token A
+    let now = 1_000_000;
+    setup.set_time(now)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expiry: now as u64 + 3600,
+        proceeds_floor: 2000,
+        proceeds_deadline: now as u64 + 7200,
+        ..MakeEscrowIx::new(EscrowType::Partial, 2000, 4000, setup.bump, setup.seed)
+    })?;
+
+    setup.advance_time(3601)?;
+    let taker = setup.taker.insecure_clone();
+    let result = setup.reclaim_expired(&taker).map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::ProceedsFloorNotMet)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.advance_time(3600)?;
+    setup.svm.expire_blockhash();
+    setup.reclaim_expired(&taker)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_finalize_respects_proceeds_floor() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expiry: now as u64 + 3600,
+        proceeds_floor: 2000,
+        proceeds_deadline: now as u64 + 7200,
+        ..MakeEscrowIx::new(EscrowType::Partial, 2000, 4000, setup.bump, setup.seed)
+    })?;
+
+    // Expired, but the floor still locks the token A
+    setup.advance_time(3601)?;
+    let result = setup.finalize_escrow().map(|_| ());
+    assert_escrow_error(result, EscrowErrorCode::ProceedsFloorNotMet)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.advance_time(3600)?;
+    setup.svm.expire_blockhash();
+    setup.finalize_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
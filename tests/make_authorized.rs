@@ -0,0 +1,109 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{authorization_message, MakeEscrowIx},
+    states::EscrowType,
+};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+
+mod common;
+pub use common::*;
+
+/// Point the setup at `authorized_maker`'s escrow and return its make params
+fn authorized_make_ix(
+    setup: &mut EscrowTestSetup,
+    authorized_maker: &Pubkey,
+) -> Result<MakeEscrowIx> {
+    let (escrow_pda, bump) = Pubkey::find_program_address(
+        &[b"Escrow", authorized_maker.as_ref(), &setup.seed],
+        &setup.program_id,
+    );
+    let token_a_mint = setup.token_a_mint;
+    setup.escrow_token_a_ata = setup_ata(&mut setup.svm, &token_a_mint, &escrow_pda, &setup.maker)?;
+    setup.escrow_pda = escrow_pda;
+    setup.bump = bump;
+
+    Ok(MakeEscrowIx::new(
+        EscrowType::Simple,
+        2500,
+        5000,
+        bump,
+        setup.seed,
+    ))
+}
+
+fn ed25519_authorization(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature: Signature = signer.sign_message(message);
+    new_ed25519_instruction_with_signature(message, &signature.into(), &signer.pubkey().to_bytes())
+}
+
+#[test]
+fn test_make_escrow_with_maker_authorization() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Make Escrow With Maker Authorization ===");
+
+    // The user authorizes off-chain; the service (setup maker) submits and funds the escrow
+    let user = Keypair::new();
+    let ix = authorized_make_ix(&mut setup, &user.pubkey())?;
+    let message = authorization_message(&setup.escrow_pda.to_bytes(), &ix);
+
+    let authorization = ed25519_authorization(&user, &message);
+    let make = setup.make_authorized_instruction(ix, &user.pubkey());
+    let service = setup.maker.insecure_clone();
+    setup.send_instructions(&[authorization, make], &[&service])?;
+
+    let escrow = setup.get_escrow(&setup.escrow_pda)?;
+    assert_eq!(
+        escrow.maker_pubkey,
+        user.pubkey().to_bytes(),
+        "The authorized user should own the escrow"
+    );
+    assert_eq!(setup.get_escrow_token_a_balance(), ix.token_a_amount);
+    assert_eq!(
+        setup.get_maker_token_a_balance(),
+        10000 - ix.token_a_amount,
+        "The service should fund token A"
+    );
+
+    println!("✅ Make escrow with maker authorization test passed");
+    Ok(())
+}
+
+#[test]
+fn test_make_escrow_authorization_must_match_params() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Make Escrow Authorization Mismatch ===");
+
+    let user = Keypair::new();
+    let ix = authorized_make_ix(&mut setup, &user.pubkey())?;
+    let message = authorization_message(&setup.escrow_pda.to_bytes(), &ix);
+    let authorization = ed25519_authorization(&user, &message);
+
+    // The service submits a lower price than the user signed
+    let mut tampered = ix;
+    tampered.token_b_amount = 1;
+    let make = setup.make_authorized_instruction(tampered, &user.pubkey());
+    let service = setup.maker.insecure_clone();
+    let result = setup.send_instructions(&[authorization, make], &[&service]);
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuthorization)?;
+
+    // A signature from someone other than the named maker is rejected too
+    let impostor = Keypair::new();
+    let authorization = ed25519_authorization(&impostor, &message);
+    let make = setup.make_authorized_instruction(ix, &user.pubkey());
+    let result = setup.send_instructions(&[authorization, make], &[&service]);
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuthorization)?;
+
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    println!("✅ Make escrow authorization mismatch test passed");
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, signer::Signer};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_make_rejects_non_signer_maker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The taker pays for a make that the maker never signed
+    let ix = MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed);
+    let mut instruction = setup.make_instruction(ix);
+    instruction.accounts[0].is_signer = false;
+
+    let taker = setup.taker.insecure_clone();
+    let result = setup.send_instructions(&[instruction], &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::InvalidMaker)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_truncated_data() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let ix = MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed);
+    let mut instruction = setup.make_instruction(ix);
+    instruction.data.truncate(1 + 10);
+
+    let maker = setup.maker.insecure_clone();
+    let result = setup.send_instructions(&[instruction], &[&maker]);
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_existing_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    let result = setup.create_escrow(EscrowType::Simple, 1500, 2500);
+    assert_escrow_error(result, EscrowErrorCode::EscrowAlreadyExists)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_error_logs_its_message() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    let err = setup
+        .create_escrow(EscrowType::Simple, 1500, 2500)
+        .expect_err("second make should fail");
+    let expected = format!(
+        "Error 6001: {}",
+        EscrowErrorCode::EscrowAlreadyExists.as_str()
+    );
+    assert!(
+        format!("{:?}", err).contains(&expected),
+        "logs should name the error: {:?}",
+        err
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_mint_not_owned_by_token_program() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // An address the token program doesn't own
+    setup.token_b_mint = Pubkey::new_unique();
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenOwner)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_empty_maker_token_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // A maker token A account that doesn't exist
+    setup.maker_token_a_ata = Pubkey::new_unique();
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidMakerTokenAccount)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_wrong_bump() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Another bump that derives a valid but different address
+    let maker = setup.maker.pubkey();
+    let wrong_bump = (0..setup.bump)
+        .rev()
+        .find(|bump| {
+            Pubkey::create_program_address(
+                &[b"Escrow", maker.as_ref(), &setup.seed, &[*bump]],
+                &setup.program_id,
+            )
+            .is_ok()
+        })
+        .expect("a lower off-curve bump exists");
+
+    let ix = MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, wrong_bump, setup.seed);
+    let result = setup.create_escrow_with_ix(ix);
+    assert_escrow_error(result, EscrowErrorCode::PdaMismatch)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_token_program_mismatch() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // A Token-2022 mint paired with the maker's classic token program accounts
+    let maker = setup.maker.pubkey();
+    setup.token_a_mint = setup_token_2022_mint(&mut setup.svm, &maker, 9);
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenProgram)?;
+    assert_eq!(
+        setup.get_token_account_balance(&setup.maker_token_a_ata),
+        10000
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_wrong_escrow_vault() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // A token A account owned by the taker rather than the escrow PDA
+    let real_vault = setup.escrow_token_a_ata;
+    setup.escrow_token_a_ata = setup.taker_token_a_ata;
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+
+    // An escrow-owned account holding the wrong mint
+    let token_b_mint = setup.token_b_mint;
+    let escrow_pda = setup.escrow_pda;
+    setup.escrow_token_a_ata =
+        setup_token_account(&mut setup.svm, &token_b_mint, &escrow_pda, &setup.maker)?;
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    setup.escrow_token_a_ata = real_vault;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_zero_amounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    for (escrow_type, token_a_amount, token_b_amount) in [
+        (EscrowType::Simple, 0, 2000),
+        (EscrowType::Simple, 1000, 0),
+        (EscrowType::Partial, 0, 2000),
+        (EscrowType::Partial, 1000, 0),
+    ] {
+        let result = setup.create_escrow(escrow_type, token_a_amount, token_b_amount);
+        assert_escrow_error(result, EscrowErrorCode::ZeroAmount)?;
+        assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    }
+
+    let result = setup.create_dutch_auction_escrow(0, 2000, 1000, 3600);
+    assert_escrow_error(result, EscrowErrorCode::ZeroAmount)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_identical_mints() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.token_b_mint = setup.token_a_mint;
+
+    let result = setup.create_escrow(EscrowType::Simple, 1000, 2000);
+    assert_escrow_error(result, EscrowErrorCode::IdenticalMints)?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_more_token_a_than_maker_holds() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The maker holds 10000 token A
+    let result = setup.create_escrow(EscrowType::Simple, 10001, 2000);
+    assert_escrow_error(result, EscrowErrorCode::InsufficientFunds)?;
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    // Escrowing exactly the balance is fine
+    setup.create_escrow(EscrowType::Simple, 10000, 2000)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 10000);
+
+    Ok(())
+}
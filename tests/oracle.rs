@@ -0,0 +1,141 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{MakeEscrowIx, TakeEscrowIx},
+    states::{EscrowType, OraclePrice},
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+mod common;
+pub use common::*;
+
+/// Write a price feed account owned by `oracle_program`
+fn set_oracle_price(
+    setup: &mut EscrowTestSetup,
+    oracle_program: &Pubkey,
+    price: OraclePrice,
+) -> Result<Pubkey> {
+    let feed = Pubkey::new_unique();
+    setup.svm.set_account(
+        feed,
+        Account {
+            lamports: 1_000_000,
+            data: price.pack().to_vec(),
+            owner: *oracle_program,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )?;
+    Ok(feed)
+}
+
+fn take_oracle(
+    setup: &mut EscrowTestSetup,
+    feed: Pubkey,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<()> {
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(
+        &TakeEscrowIx::new(EscrowType::Oracle, token_a_amount, token_b_amount).pack(),
+    );
+    let take = setup.take_instruction(ix_data, feed);
+    let taker = setup.taker.insecure_clone();
+    setup.send_instructions(&[take], &[&taker])
+}
+
+#[test]
+fn test_oracle_escrow_take_at_feed_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Oracle Escrow Take ===");
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+
+    // 2.5 token B per token A
+    let price = OraclePrice {
+        price: 25,
+        exponent: 1,
+        publish_time: now,
+    };
+    let oracle_program = Pubkey::new_unique();
+    let feed = set_oracle_price(&mut setup, &oracle_program, price)?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        oracle_program: oracle_program.to_bytes(),
+        oracle_max_age: 60,
+        oracle_feed: feed.to_bytes(),
+        ..MakeEscrowIx::new(EscrowType::Oracle, 1000, 0, setup.bump, setup.seed)
+    })?;
+
+    let result = take_oracle(&mut setup, feed, 400, 999);
+    assert_escrow_error(result, EscrowErrorCode::InsufficientFunds)?;
+
+    // The taker offers more than the feed price but is charged exactly that
+    take_oracle(&mut setup, feed, 400, 1200)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 1000);
+    assert_eq!(setup.get_escrow_token_a_balance(), 600);
+
+    // A feed from another program is rejected
+    let impostor = set_oracle_price(&mut setup, &Pubkey::new_unique(), price)?;
+    let result = take_oracle(&mut setup, impostor, 400, 1000);
+    assert!(result.is_err(), "A foreign price feed should be rejected");
+
+    // Once the price is older than the escrow's max age, takes stop
+    setup.advance_time(61)?;
+    let result = take_oracle(&mut setup, feed, 400, 1000);
+    assert_escrow_error(result, EscrowErrorCode::StaleOraclePrice)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 600);
+
+    println!("✅ Oracle escrow take test passed");
+    Ok(())
+}
+
+#[test]
+fn test_oracle_take_rejects_other_feeds_of_the_same_oracle() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+
+    // One oracle program publishes feeds for several assets
+    let oracle_program = Pubkey::new_unique();
+    let price = |price| OraclePrice {
+        price,
+        exponent: 0,
+        publish_time: now,
+    };
+    let feed = set_oracle_price(&mut setup, &oracle_program, price(3))?;
+    let cheap_feed = set_oracle_price(&mut setup, &oracle_program, price(1))?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        oracle_program: oracle_program.to_bytes(),
+        oracle_max_age: 60,
+        oracle_feed: feed.to_bytes(),
+        ..MakeEscrowIx::new(EscrowType::Oracle, 1000, 0, setup.bump, setup.seed)
+    })?;
+
+    let result = take_oracle(&mut setup, cheap_feed, 1000, 1000);
+    assert_escrow_error(result, EscrowErrorCode::InvalidOracleFeed)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    take_oracle(&mut setup, feed, 1000, 3000)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 3000);
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_oracle_escrow_without_feed() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let result = setup.create_escrow_with_ix(MakeEscrowIx {
+        oracle_program: Pubkey::new_unique().to_bytes(),
+        oracle_max_age: 60,
+        ..MakeEscrowIx::new(EscrowType::Oracle, 1000, 0, setup.bump, setup.seed)
+    });
+    assert_escrow_error(result, EscrowErrorCode::InvalidOracleFeed)?;
+
+    Ok(())
+}
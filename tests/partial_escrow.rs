@@ -1,5 +1,11 @@
 use anyhow::Result;
-use escrow_suite::states::EscrowType;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    events::{EscrowEvent, EscrowEventKind, EVENT_LOG_PREFIX},
+    instructions::{MakeEscrowIx, TakeEscrowIx, TakeLimitIx, LIMIT_PRICE_SCALE},
+    states::{EscrowType, FILL_HISTORY_LEN},
+};
+use solana_sdk::{instruction::InstructionError, signer::Signer};
 
 mod common;
 pub use common::*;
@@ -319,7 +325,8 @@ fn test_partial_escrow_precision_handling() -> Result<()> {
     // Take amount that will result in fractional token B
     setup.take_partial_escrow(take_amount)?;
 
-    let expected_token_b = (total_token_b * take_amount) / total_token_a;
+    // Fractional token B rounds up in the maker's favor
+    let expected_token_b = (total_token_b * take_amount).div_ceil(total_token_a);
     let remaining_token_a = total_token_a - take_amount;
 
     setup.verify_partial_escrow_balances(
@@ -335,6 +342,33 @@ fn test_partial_escrow_precision_handling() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_partial_escrow_drain_pays_full_token_b() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 1000;
+    let total_token_b = 3333; // Not evenly divisible
+
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+
+    // Every take but the last leaves a fractional share of token B
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    for take_amount in [333, 334, 7, 326] {
+        setup.take_partial_escrow(take_amount)?;
+    }
+    assert_eq!(setup.get_escrow_token_a_balance(), 0);
+
+    let maker_token_b_received = setup.get_maker_token_b_balance() - maker_token_b_before;
+    assert!(
+        maker_token_b_received >= total_token_b,
+        "Maker received {} of {} token B",
+        maker_token_b_received,
+        total_token_b
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_partial_escrow_sequential_takes() -> Result<()> {
     let mut setup = EscrowTestSetup::new()?;
@@ -415,3 +449,630 @@ fn test_partial_escrow_sequential_takes() -> Result<()> {
     println!("✅ Partial escrow sequential takes test passed");
     Ok(())
 }
+
+#[test]
+fn test_partial_escrow_fill_cooldown() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 6000;
+    let total_token_b = 12000;
+    let fill_cooldown = 60;
+
+    println!("=== Testing Partial Escrow Fill Cooldown ===");
+    println!("Fill Cooldown: {} seconds", fill_cooldown);
+
+    setup.set_time(1_000_000)?;
+
+    // Create a partial escrow with a cooldown between fills
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Partial,
+        total_token_a,
+        total_token_b,
+        setup.bump,
+        setup.seed,
+    );
+    ix.fill_cooldown = fill_cooldown;
+    setup.create_escrow_with_ix(ix)?;
+
+    // First fill is accepted
+    let first_take = 2000;
+    setup.take_partial_escrow(first_take)?;
+    assert_eq!(
+        setup.get_escrow_token_a_balance(),
+        total_token_a - first_take
+    );
+
+    // An immediate second fill is rejected
+    let result = setup.take_partial_escrow(1500);
+    assert_escrow_error(result, EscrowErrorCode::CooldownActive)?;
+    assert_eq!(
+        setup.get_escrow_token_a_balance(),
+        total_token_a - first_take,
+        "Rejected fill should not move tokens"
+    );
+
+    // Once the cooldown has elapsed the next fill goes through
+    setup.advance_time(fill_cooldown as i64 + 1)?;
+    let second_take = 1000;
+    setup.take_partial_escrow(second_take)?;
+    assert_eq!(
+        setup.get_escrow_token_a_balance(),
+        total_token_a - first_take - second_take
+    );
+
+    println!("✅ Partial escrow fill cooldown test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_zero_take_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 6000;
+    let total_token_b = 12000;
+    let fill_cooldown = 60;
+
+    println!("=== Testing Partial Escrow Zero Take ===");
+
+    setup.set_time(1_000_000)?;
+
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Partial,
+        total_token_a,
+        total_token_b,
+        setup.bump,
+        setup.seed,
+    );
+    ix.fill_cooldown = fill_cooldown;
+    setup.create_escrow_with_ix(ix)?;
+
+    // An empty fill is rejected instead of restarting the cooldown
+    let result = setup.take_partial_escrow(0);
+    assert_escrow_error(result, EscrowErrorCode::ExceedsEscrowBalance)?;
+    assert!(setup.query_fill_history()?.is_empty());
+
+    // So a real fill can still go through straight away
+    let take = 2000;
+    setup.take_partial_escrow(take)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), total_token_a - take);
+
+    println!("✅ Partial escrow zero take test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_fill_history() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 1000;
+    let total_token_b = 1000;
+    let fill_time = 1_000_000;
+
+    println!("=== Testing Partial Escrow Fill History ===");
+
+    setup.set_time(fill_time)?;
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+
+    // More fills than the history can hold, with distinct amounts
+    let takes: Vec<u64> = (10..20).collect();
+    for take_amount in &takes {
+        setup.take_partial_escrow(*take_amount)?;
+    }
+
+    let history = setup.query_fill_history()?;
+    assert_eq!(history.len(), FILL_HISTORY_LEN);
+
+    // Most recent fill first, oldest fills dropped
+    for (record, expected_amount) in history.iter().zip(takes.iter().rev()) {
+        println!("Fill: {:?}", record);
+        assert_eq!(record.0, setup.taker.pubkey());
+        assert_eq!(record.1, *expected_amount);
+        assert_eq!(record.2, fill_time as u64);
+    }
+
+    println!("✅ Partial escrow fill history test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_tampered_take_params_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 4000;
+    let total_token_b = 8000;
+
+    println!("=== Testing Partial Escrow Take Parameter Commitment ===");
+
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+
+    // The taker commits to taking 1000 token A
+    let committed = TakeEscrowIx::new(EscrowType::Partial, 1000, 0);
+    let params_hash = committed.hash_params(&setup.escrow_pda.to_bytes());
+
+    // A relayer swaps in a larger amount but keeps the taker's commitment
+    let tampered = TakeEscrowIx::new(EscrowType::Partial, 3000, 0);
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&tampered.pack());
+    ix_data.extend_from_slice(&params_hash);
+
+    let result = setup.take_escrow_with_data(ix_data);
+    assert_escrow_error(result, EscrowErrorCode::ParamsHashMismatch)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), total_token_a);
+
+    // The untampered parameters are accepted
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&committed.pack());
+    ix_data.extend_from_slice(&params_hash);
+    setup.take_escrow_with_data(ix_data)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), total_token_a - 1000);
+
+    println!("✅ Partial escrow take parameter commitment test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_tampered_split_amount_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 4000;
+    let total_token_b = 8000;
+
+    println!("=== Testing Partial Escrow Split Amount Commitment ===");
+
+    let taker = setup.taker.pubkey();
+    let token_b_mint = setup.token_b_mint;
+    let taker_token_b_ata_2 =
+        setup_token_account(&mut setup.svm, &token_b_mint, &taker, &setup.maker)?;
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &setup.maker,
+        &taker_token_b_ata_2,
+        2000,
+    )?;
+
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+
+    // The taker commits to paying 500 of the 2000 token B from their second account
+    let mut committed = TakeEscrowIx::new(EscrowType::Partial, 1000, 0);
+    committed.split_token_b_amount = Some(500);
+    committed.params_hash = Some(committed.hash_params(&setup.escrow_pda.to_bytes()));
+
+    // A relayer changes only the split amount
+    let mut tampered = committed.clone();
+    tampered.split_token_b_amount = Some(2000);
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&tampered.pack_extended());
+    let instruction = setup.take_instruction(ix_data, taker_token_b_ata_2);
+    let taker = setup.taker.insecure_clone();
+    let result = setup.send_instructions(&[instruction], &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::ParamsHashMismatch)?;
+    assert_eq!(setup.get_token_account_balance(&taker_token_b_ata_2), 2000);
+
+    // The committed split is accepted
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&committed.pack_extended());
+    let instruction = setup.take_instruction(ix_data, taker_token_b_ata_2);
+    setup.send_instructions(&[instruction], &[&taker])?;
+    assert_eq!(setup.get_escrow_token_a_balance(), total_token_a - 1000);
+    assert_eq!(setup.get_token_account_balance(&taker_token_b_ata_2), 1500);
+
+    println!("✅ Partial escrow split amount commitment test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_volume_discount() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 5000;
+    let total_token_b = 10000;
+
+    println!("=== Testing Partial Escrow Volume Discount ===");
+
+    // 5% off the token B charged for takes above 1000 token A
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Partial,
+        total_token_a,
+        total_token_b,
+        setup.bump,
+        setup.seed,
+    );
+    ix.discount_threshold = 1000;
+    ix.volume_discount_bps = 500;
+    setup.create_escrow_with_ix(ix)?;
+
+    // Above the threshold: 2000 token A at base 4000 token B, discounted to 3800
+    setup.take_partial_escrow(2000)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 3800);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 3800);
+
+    // Below the threshold: 600 of the remaining 3000 token A at the full 1200 token B
+    setup.take_partial_escrow(600)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 3800 + 1200);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 3800 - 1200);
+
+    println!("✅ Partial escrow volume discount test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_exact_full_take_closes_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 3000;
+    let total_token_b = 6000;
+
+    println!("=== Testing Partial Escrow Exact Full Take ===");
+
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+    let maker_lamports = setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0);
+    let escrow_rent = setup.svm.get_balance(&setup.escrow_pda).unwrap_or(0)
+        + setup
+            .svm
+            .get_balance(&setup.escrow_token_a_ata)
+            .unwrap_or(0);
+
+    // One partial take of everything that remains
+    setup.take_partial_escrow(total_token_a)?;
+
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + total_token_a);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + total_token_b);
+    assert!(
+        setup
+            .svm
+            .get_account(&setup.escrow_pda)
+            .is_none_or(|a| a.data.is_empty()),
+        "Escrow account should be closed"
+    );
+    assert!(
+        setup
+            .svm
+            .get_account(&setup.escrow_token_a_ata)
+            .is_none_or(|a| a.data.is_empty()),
+        "Escrow token A account should be closed"
+    );
+    assert_eq!(
+        setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0),
+        maker_lamports + escrow_rent,
+        "Escrow rent should be refunded to the maker"
+    );
+
+    println!("✅ Partial escrow exact full take test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_withdraw_then_take_rest() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Partial Escrow Withdraw ===");
+
+    setup.create_escrow(EscrowType::Partial, 4000, 8000)?;
+    setup.take_partial_escrow(1000)?;
+
+    // Pull half of the remaining 3000 token A back; the ask shrinks at the same rate
+    setup.withdraw_escrow(1500)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 4000 + 1500);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1500);
+
+    // Withdrawing more than remains is rejected
+    let result = setup.withdraw_escrow(1501);
+    assert_escrow_error(result, EscrowErrorCode::InsufficientFunds)?;
+
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_partial_escrow(1500)?;
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        3000,
+        "The rest should be priced at the original rate"
+    );
+
+    println!("✅ Partial escrow withdraw test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_decimals_normalized_pricing() -> Result<()> {
+    // 6-decimal token A priced in 9-decimal token B
+    let mut setup = EscrowTestSetup::with_decimals(6, 9)?;
+
+    println!("=== Testing Decimals-Normalized Partial Pricing ===");
+
+    let taker_token_b_ata = setup.taker_token_b_ata;
+    let token_b_mint = setup.token_b_mint;
+    let mint_authority = setup.maker.insecure_clone();
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &mint_authority,
+        &taker_token_b_ata,
+        10_000_000,
+    )?;
+
+    // 4000 token A for 8000 token B, both quoted at token A's 6 decimals
+    let mut ix = MakeEscrowIx::new(EscrowType::Partial, 4000, 8000, setup.bump, setup.seed);
+    ix.price_mode = MakeEscrowIx::PRICE_MODE_DECIMALS_NORMALIZED;
+    setup.create_escrow_with_ix(ix)?;
+
+    // A quarter of the lot costs 2000 at 6 decimals, i.e. 2_000_000 token B base units
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_partial_escrow(1000)?;
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        2_000_000
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    println!("✅ Decimals-normalized partial pricing test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_take_overflow_is_rejected() -> Result<()> {
+    // 6-decimal token A priced in 18-decimal token B
+    let mut setup = EscrowTestSetup::with_decimals(6, 18)?;
+
+    println!("=== Testing Partial Take Overflow ===");
+
+    // An ask near u64::MAX at token A's decimals can't be expressed in token B base units
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Partial,
+        4000,
+        u64::MAX - 1,
+        setup.bump,
+        setup.seed,
+    );
+    ix.price_mode = MakeEscrowIx::PRICE_MODE_DECIMALS_NORMALIZED;
+    setup.create_escrow_with_ix(ix)?;
+
+    let result = setup.take_partial_escrow(1000);
+    assert_escrow_error(result, EscrowErrorCode::ArithmeticOverflow)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 4000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+    assert_eq!(
+        setup.get_escrow(&setup.escrow_pda)?.token_b_amount,
+        u64::MAX - 1
+    );
+
+    println!("✅ Partial take overflow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_two_takes_in_one_transaction() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Two Partial Takes In One Transaction ===");
+
+    setup.create_escrow(EscrowType::Partial, 4000, 8000)?;
+    let taker = setup.taker.insecure_clone();
+    let take = |setup: &EscrowTestSetup, token_a_amount: u64| {
+        let mut ix_data = vec![0x02];
+        ix_data
+            .extend_from_slice(&TakeEscrowIx::new(EscrowType::Partial, token_a_amount, 0).pack());
+        setup.take_instruction(ix_data, setup.program_id)
+    };
+
+    // The second take sees the 1000 left by the first, so together they can't overdraw
+    let overdraw = [take(&setup, 3000), take(&setup, 2000)];
+    let result = setup.send_instructions(&overdraw, &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::ExceedsEscrowBalance)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 4000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+
+    // Takes that fit within the supply both land, in instruction order
+    let fill = [take(&setup, 3000), take(&setup, 1000)];
+    setup.send_instructions(&fill, &[&taker])?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 4000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 8000);
+    assert!(
+        setup.svm.get_account(&setup.escrow_pda).is_none(),
+        "The second take should close the emptied escrow"
+    );
+
+    println!("✅ Two partial takes in one transaction test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_take_at_limit_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Partial Take At Limit Price ===");
+
+    // 2000 token A offered at 2 token B each
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+
+    // A limit of 1.5 token B per token A is below the escrow's price
+    let result = setup.take_limit(TakeLimitIx {
+        max_token_a: 1000,
+        limit_price_per_a: LIMIT_PRICE_SCALE * 3 / 2,
+    });
+    assert_escrow_error(result, EscrowErrorCode::LimitPriceExceeded)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000);
+
+    // A limit of 2.5 fills everything left, even though the taker would have taken more
+    setup.take_limit(TakeLimitIx {
+        max_token_a: 5000,
+        limit_price_per_a: LIMIT_PRICE_SCALE * 5 / 2,
+    })?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 2000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 4000);
+    assert!(
+        setup.svm.get_account(&setup.escrow_pda).is_none(),
+        "Filling the remainder should close the escrow"
+    );
+
+    println!("✅ Partial take at limit price test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_fills_disabled() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Partial Escrow Fills Disabled ===");
+
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    setup.set_fills_enabled(false)?;
+
+    let result = setup.take_partial_escrow(500);
+    assert_escrow_error(result, EscrowErrorCode::FillsDisabled)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.set_fills_enabled(true)?;
+    setup.svm.expire_blockhash();
+    setup.take_partial_escrow(500)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1500);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 1000);
+
+    println!("✅ Partial escrow fills disabled test passed");
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_take_requires_body() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+
+    let result = setup.take_escrow();
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 2000);
+
+    setup.take_partial_escrow(1000)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_logs_structured_events() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let event = |kind, token_a_amount, token_b_amount| {
+        let encoded = EscrowEvent {
+            kind,
+            escrow_type: EscrowType::Partial,
+            maker: setup.maker.pubkey().to_bytes(),
+            seed: setup.seed,
+            token_a_mint: setup.token_a_mint.to_bytes(),
+            token_b_mint: setup.token_b_mint.to_bytes(),
+            token_a_amount,
+            token_b_amount,
+        }
+        .encode();
+        format!(
+            "Program log: {}{}",
+            EVENT_LOG_PREFIX,
+            String::from_utf8(encoded.to_vec()).unwrap()
+        )
+    };
+    let made = event(EscrowEventKind::Made, 1000, 2000);
+    let taken = event(EscrowEventKind::Taken, 250, 500);
+
+    let make = setup.make_instruction(MakeEscrowIx::new(
+        EscrowType::Partial,
+        1000,
+        2000,
+        setup.bump,
+        setup.seed,
+    ));
+    let maker = setup.maker.insecure_clone();
+    let logs = setup.send_instructions_with_logs(&[make], &[&maker])?;
+    assert!(logs.contains(&made), "{:?}", logs);
+
+    // Take events carry the amounts this take actually moved
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Partial, 250, 0).pack());
+    let take = setup.take_instruction(ix_data, setup.program_id);
+    let taker = setup.taker.insecure_clone();
+    let logs = setup.send_instructions_with_logs(&[take], &[&taker])?;
+    assert!(logs.contains(&taken), "{:?}", logs);
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_deposit_then_take_enlarged_lot() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Partial, 1000, 2000)?;
+
+    // Token B must grow in proportion, keeping the price at 2 token B per token A
+    let result = setup.deposit_escrow(500, 900);
+    assert_escrow_error(result, EscrowErrorCode::InvalidDepositRatio)?;
+
+    setup.deposit_escrow(500, 1000)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1500);
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 1500);
+    let escrow = setup.get_escrow(&setup.escrow_pda)?;
+    assert_eq!(escrow.token_a_amount, 1500);
+    assert_eq!(escrow.token_b_amount, 3000);
+
+    setup.take_partial_escrow(1500)?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1500);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 3000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_escrow_take_reports_which_limit_is_exceeded() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // 1000 token A for 40000 token B; the taker holds 10000 token B
+    setup.create_escrow(EscrowType::Partial, 1000, 40000)?;
+
+    let result = setup.take_partial_escrow(1001);
+    assert_escrow_error(result, EscrowErrorCode::ExceedsEscrowBalance)?;
+
+    // 500 token A costs 20000 token B
+    let result = setup.take_partial_escrow(500);
+    assert_escrow_error(result, EscrowErrorCode::TakerInsufficientTokenB)?;
+
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_all_or_nothing_partial_escrow_rejects_partial_takes() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        all_or_nothing: true,
+        ..MakeEscrowIx::new(EscrowType::Partial, 4000, 8000, setup.bump, setup.seed)
+    })?;
+
+    let result = setup.take_partial_escrow(1000);
+    assert_escrow_error(result, EscrowErrorCode::PartialFillNotAllowed)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 4000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    // The whole lot is still priced proportionally
+    setup.take_partial_escrow(4000)?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 4000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 8000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_make_rejects_all_or_nothing_outside_partial_escrows() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let result = setup.create_escrow_with_ix(MakeEscrowIx {
+        all_or_nothing: true,
+        ..MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed)
+    });
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowType)?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_private_escrow_only_takeable_by_allowed_taker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Private Escrow ===");
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        allowed_taker: setup.taker.pubkey().to_bytes(),
+        ..MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed)
+    })?;
+
+    // Any other wallet is turned away, whatever accounts it passes
+    let allowed_taker = setup.taker.insecure_clone();
+    let outsider = Keypair::new();
+    setup
+        .svm
+        .airdrop(&outsider.pubkey(), 10_000_000)
+        .map_err(|e| anyhow::anyhow!("Failed to airdrop: {:?}", e))?;
+    setup.taker = outsider;
+
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::TakerNotAllowed)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    setup.taker = allowed_taker;
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    println!("✅ Private escrow test passed");
+    Ok(())
+}
@@ -1,5 +1,17 @@
 use anyhow::Result;
-use escrow_suite::states::EscrowType;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{MakeEscrowIx, TakeEscrowIx},
+    states::{Escrow, EscrowStatus, EscrowType, ESCROW_PREFIXES},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::instruction::transfer;
 
 mod common;
 pub use common::*;
@@ -223,3 +235,789 @@ fn test_simple_escrow_multiple_escrows() -> Result<()> {
     println!("✅ Multiple simple escrows test passed");
     Ok(())
 }
+
+#[test]
+fn test_simple_escrow_frozen_vault_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 2000;
+    let token_b_amount = 4000;
+
+    println!("=== Testing Simple Escrow with Frozen Vault ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+
+    // Freeze the escrow's token A account
+    let escrow_token_a_ata = setup.escrow_token_a_ata;
+    setup.freeze_token_account(&escrow_token_a_ata)?;
+
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::FrozenAccount)?;
+
+    // Balances are untouched by the rejected take
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    println!("✅ Simple escrow frozen vault test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_proceeds_to_designated_vault() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 3000;
+    let token_b_amount = 6000;
+
+    println!("=== Testing Simple Escrow Proceeds Vault ===");
+
+    // A token B account controlled by someone other than the maker
+    let vault_owner = Keypair::new();
+    let token_b_mint = setup.token_b_mint;
+    let proceeds_vault = setup_ata(
+        &mut setup.svm,
+        &token_b_mint,
+        &vault_owner.pubkey(),
+        &setup.maker,
+    )?;
+
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Simple,
+        token_a_amount,
+        token_b_amount,
+        setup.bump,
+        setup.seed,
+    );
+    ix.token_b_vault = proceeds_vault.to_bytes();
+    setup.create_escrow_with_ix(ix)?;
+
+    // Delivering to the maker's own ATA is rejected
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidProceedsVault)?;
+
+    // Delivering to the designated vault succeeds
+    setup.maker_token_b_ata = proceeds_vault;
+    setup.take_escrow()?;
+
+    assert_eq!(
+        setup.get_balance(&vault_owner.pubkey(), &token_b_mint),
+        token_b_amount,
+        "Proceeds should arrive in the designated vault"
+    );
+    assert_eq!(
+        setup.get_maker_token_b_balance(),
+        10000,
+        "Maker's own token B ATA should be untouched"
+    );
+    assert_eq!(
+        setup.get_taker_token_a_balance(),
+        10000 + token_a_amount,
+        "Taker should receive the escrowed token A"
+    );
+
+    println!("✅ Simple escrow proceeds vault test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_aliased_accounts_rejected() -> Result<()> {
+    println!("=== Testing Simple Escrow Aliased Accounts ===");
+
+    let token_a_amount = 2000;
+    let token_b_amount = 4000;
+
+    // Taker's token A destination aliased to the escrow vault
+    let mut setup = EscrowTestSetup::new()?;
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+    setup.taker_token_a_ata = setup.escrow_token_a_ata;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::DuplicateAccount)?;
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    // Taker's token B source aliased to the maker's token B destination
+    let mut setup = EscrowTestSetup::new()?;
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+    setup.taker_token_b_ata = setup.maker_token_b_ata;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::DuplicateAccount)?;
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    println!("✅ Simple escrow aliased accounts test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_split_payment() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 3000;
+    let token_b_amount = 12000;
+    let split_amount = 5000;
+
+    println!("=== Testing Simple Escrow Split Payment ===");
+
+    // The taker holds token B across their ATA and a second account
+    let taker = setup.taker.pubkey();
+    let token_b_mint = setup.token_b_mint;
+    let taker_token_b_ata_2 =
+        setup_token_account(&mut setup.svm, &token_b_mint, &taker, &setup.maker)?;
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &setup.maker,
+        &taker_token_b_ata_2,
+        split_amount,
+    )?;
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+
+    let take_ix = TakeEscrowIx::new(EscrowType::Simple, token_a_amount, token_b_amount);
+    setup.take_escrow_split_payment(take_ix, split_amount, taker_token_b_ata_2)?;
+
+    assert_eq!(
+        setup.get_maker_token_b_balance(),
+        10000 + token_b_amount,
+        "Maker should receive the combined token B payment"
+    );
+    assert_eq!(
+        setup.get_taker_token_b_balance(),
+        10000 - (token_b_amount - split_amount),
+        "Primary token B account should cover the remainder"
+    );
+    assert_eq!(
+        setup.get_token_account_balance(&taker_token_b_ata_2),
+        0,
+        "Second token B account should cover the split amount"
+    );
+    assert_eq!(
+        setup.get_taker_token_a_balance(),
+        10000 + token_a_amount,
+        "Taker should receive the escrowed token A"
+    );
+
+    println!("✅ Simple escrow split payment test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_with_permuted_trailing_accounts() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let taker = setup.taker.pubkey();
+    let token_b_mint = setup.token_b_mint;
+    let taker_token_b_ata_2 =
+        setup_token_account(&mut setup.svm, &token_b_mint, &taker, &setup.maker)?;
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &setup.maker,
+        &taker_token_b_ata_2,
+        500,
+    )?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Programs first, the config account last and the split account in between
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Simple, 1000, 2000).pack());
+    ix_data.extend_from_slice(&500u64.to_le_bytes());
+    let mut take = setup.take_instruction(ix_data, taker_token_b_ata_2);
+    take.accounts[7..].reverse();
+
+    let taker = setup.taker.insecure_clone();
+    setup.send_instructions(&[take], &[&taker])?;
+
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 2000);
+    assert_eq!(setup.get_token_account_balance(&taker_token_b_ata_2), 0);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_per_unit_price() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 1500;
+    let unit_price = 4;
+    let total_price = token_a_amount * unit_price;
+
+    println!("=== Testing Simple Escrow Per-Unit Price ===");
+
+    let mut ix = MakeEscrowIx::new(
+        EscrowType::Simple,
+        token_a_amount,
+        unit_price,
+        setup.bump,
+        setup.seed,
+    );
+    ix.price_mode = MakeEscrowIx::PRICE_MODE_PER_UNIT;
+    setup.create_escrow_with_ix(ix)?;
+
+    setup.take_escrow()?;
+
+    // The full take charges the unit price times the escrowed amount
+    setup.verify_simple_escrow_balances(token_a_amount, total_price, "after_take")?;
+
+    println!("✅ Simple escrow per-unit price test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_composes_with_other_instructions() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 3000;
+    let token_b_amount = 14000;
+    let borrowed = 4000;
+
+    println!("=== Testing Simple Escrow Take Composability ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+
+    // Borrow token B, take the escrow, and repay with token A, all in one transaction
+    let maker = setup.maker.insecure_clone();
+    let taker = setup.taker.insecure_clone();
+    let borrow = transfer(
+        &spl_token::ID,
+        &setup.maker_token_b_ata,
+        &setup.taker_token_b_ata,
+        &maker.pubkey(),
+        &[],
+        borrowed,
+    )?;
+    let take = setup.take_instruction(vec![0x02], setup.program_id);
+    let repay = transfer(
+        &spl_token::ID,
+        &setup.taker_token_a_ata,
+        &setup.maker_token_a_ata,
+        &taker.pubkey(),
+        &[],
+        token_a_amount,
+    )?;
+    setup.send_instructions(&[borrow, take, repay], &[&taker, &maker])?;
+
+    assert_eq!(
+        setup.get_maker_token_b_balance(),
+        10000 - borrowed + token_b_amount,
+        "Maker should receive the full payment after lending token B"
+    );
+    assert_eq!(
+        setup.get_taker_token_b_balance(),
+        10000 + borrowed - token_b_amount,
+        "Taker should pay with borrowed token B"
+    );
+    assert_eq!(
+        setup.get_maker_token_a_balance(),
+        10000,
+        "Token A should be repaid to the maker later in the same transaction"
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+    assert_eq!(setup.get_escrow_token_a_balance(), 0);
+
+    println!("✅ Simple escrow take composability test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_wrong_maker_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 2000;
+    let token_b_amount = 4000;
+
+    println!("=== Testing Simple Escrow Wrong Maker ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+
+    // Substitute a maker account that didn't create the escrow
+    let mut take = setup.take_instruction(vec![0x02], setup.program_id);
+    take.accounts[2].pubkey = Pubkey::new_unique();
+    let taker = setup.taker.insecure_clone();
+    let result = setup.send_instructions(&[take], &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::InvalidMaker)?;
+
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    println!("✅ Simple escrow wrong maker test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_foreign_owned_escrow_rejected() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let token_a_amount = 2000;
+    let token_b_amount = 4000;
+
+    println!("=== Testing Simple Escrow Foreign-Owned Escrow Account ===");
+
+    setup.create_escrow(EscrowType::Simple, token_a_amount, token_b_amount)?;
+
+    // A lookalike account with escrow-shaped bytes owned by another program
+    let mut lookalike = setup
+        .svm
+        .get_account(&setup.escrow_pda)
+        .ok_or_else(|| anyhow::anyhow!("Escrow account not found"))?;
+    lookalike.owner = Pubkey::new_unique();
+    let lookalike_address = Pubkey::new_unique();
+    setup.svm.set_account(lookalike_address, lookalike)?;
+
+    let real_escrow_pda = setup.escrow_pda;
+    setup.escrow_pda = lookalike_address;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowOwner)?;
+
+    setup.escrow_pda = real_escrow_pda;
+    setup.verify_simple_escrow_balances(token_a_amount, token_b_amount, "after_creation")?;
+
+    println!("✅ Simple escrow foreign-owned escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_taker_pays_native_sol() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Take With Native SOL ===");
+
+    // Token A priced in wrapped SOL
+    let native_mint = setup_native_mint(&mut setup.svm);
+    let maker = setup.maker.pubkey();
+    let payer = setup.maker.insecure_clone();
+    setup.maker_token_b_ata = setup_ata(&mut setup.svm, &native_mint, &maker, &payer)?;
+    setup.token_b_mint = native_mint;
+
+    let price = 1_000_000;
+    setup.create_escrow(EscrowType::Simple, 2000, price)?;
+
+    let taker = setup.taker.pubkey();
+    let taker_lamports_before = setup.svm.get_balance(&taker).unwrap_or(0);
+
+    // Wrap more than needed; the surplus comes back when the temporary account closes
+    setup.take_escrow_with_sol(price + 500_000)?;
+
+    let fee = 5000;
+    assert_eq!(
+        taker_lamports_before - setup.svm.get_balance(&taker).unwrap_or(0),
+        price + fee,
+        "The taker should pay exactly the price plus the transaction fee"
+    );
+    assert_eq!(
+        setup.get_token_account_balance(&setup.maker_token_b_ata),
+        price
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 2000);
+
+    let taker_wsol_ata = get_associated_token_address(&taker, &native_mint);
+    assert!(
+        setup.svm.get_account(&taker_wsol_ata).is_none(),
+        "The temporary wSOL account should be closed"
+    );
+
+    println!("✅ Take with native SOL test passed");
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_rejects_stray_body() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Take params without a split payment have no meaning for a simple escrow
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Simple, 1000, 2000).pack());
+    let result = setup.take_escrow_with_data(ix_data);
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+
+    // Arbitrary bytes after the discriminator
+    let result = setup.take_escrow_with_data(vec![0x02, 0xAA, 0xBB, 0xCC]);
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+
+    // A split payment body declared for another escrow type
+    let mut ix_data = vec![0x02];
+    ix_data.extend_from_slice(&TakeEscrowIx::new(EscrowType::Partial, 1000, 2000).pack());
+    ix_data.extend_from_slice(&500u64.to_le_bytes());
+    let result = setup.take_escrow_with_data(ix_data);
+    assert_instruction_error(result, InstructionError::InvalidInstructionData)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    // Nothing but the discriminator
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_events_carry_escrow_id() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Indexers compute the id from the maker and seed alone
+    let escrow_id = Escrow::escrow_id(&setup.maker.pubkey().to_bytes(), &setup.seed);
+
+    let make = setup.make_instruction(MakeEscrowIx::new(
+        EscrowType::Simple,
+        1000,
+        2000,
+        setup.bump,
+        setup.seed,
+    ));
+    let maker = setup.maker.insecure_clone();
+    let logs = setup.send_instructions_with_logs(&[make], &[&maker])?;
+    let made = format!("ESCROW_MADE id={}", escrow_id);
+    assert!(logs.iter().any(|log| log.contains(&made)), "{:?}", logs);
+
+    let take = setup.take_instruction(vec![0x02], setup.program_id);
+    let taker = setup.taker.insecure_clone();
+    let logs = setup.send_instructions_with_logs(&[take], &[&taker])?;
+    let taken = format!("ESCROW_TAKEN id={} token_a_amount=1000", escrow_id);
+    assert!(logs.iter().any(|log| log.contains(&taken)), "{:?}", logs);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_closes_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let escrow_rent = setup.svm.get_balance(&setup.escrow_pda).unwrap_or(0)
+        + setup
+            .svm
+            .get_balance(&setup.escrow_token_a_ata)
+            .unwrap_or(0);
+    let maker_lamports = setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0);
+
+    setup.take_escrow()?;
+
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+    assert!(setup.svm.get_account(&setup.escrow_token_a_ata).is_none());
+    assert_eq!(
+        setup.svm.get_balance(&setup.maker.pubkey()).unwrap_or(0),
+        maker_lamports + escrow_rent,
+        "The maker should get both accounts' rent back"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_rejects_take_after_expiry() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expiry: now as u64 + 600,
+        ..MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed)
+    })?;
+
+    setup.advance_time(601)?;
+    assert_eq!(setup.query_status()?, EscrowStatus::Expired as u8);
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::EscrowExpired)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    // The maker can still cancel an expired escrow
+    setup.cancel_escrow()?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_before_expiry() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let now = 1_000_000;
+    setup.set_time(now)?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        expiry: now as u64 + 600,
+        ..MakeEscrowIx::new(EscrowType::Simple, 1000, 2000, setup.bump, setup.seed)
+    })?;
+
+    setup.advance_time(600)?;
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrows_with_distinct_eight_byte_seeds() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Order ids that only differ above the low two bytes
+    let first_order = 0x0000_0001_0000_0042u64.to_le_bytes();
+    let second_order = 0x0000_0002_0000_0042u64.to_le_bytes();
+
+    setup.use_seed(first_order)?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let first = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    setup.use_seed(second_order)?;
+    setup.create_escrow(EscrowType::Simple, 1500, 2500)?;
+    assert_ne!(setup.escrow_pda, first.0);
+
+    assert_eq!(setup.get_token_account_balance(&first.1), 1000);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1500);
+    assert_eq!(setup.get_maker_token_a_balance(), 10000 - 2500);
+
+    // Each escrow stores its own seed
+    for (escrow, seed) in [(first.0, first_order), (setup.escrow_pda, second_order)] {
+        assert_eq!(setup.get_escrow(&escrow)?.seed, seed);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_rejects_foreign_owner_at_escrow_address() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // The escrow's own address and bytes, reassigned to another program
+    let escrow_pda = setup.escrow_pda;
+    let mut account = setup
+        .svm
+        .get_account(&escrow_pda)
+        .ok_or_else(|| anyhow::anyhow!("Escrow account not found"))?;
+    account.owner = Pubkey::new_unique();
+    setup.svm.set_account(escrow_pda, account)?;
+
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowOwner)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_rejects_wrong_maker_token_b_account() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let maker_token_b_ata = setup.maker_token_b_ata;
+
+    // The maker's token A account holds the wrong mint
+    setup.maker_token_b_ata = setup.maker_token_a_ata;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenMint)?;
+
+    // A token B account the maker doesn't own
+    let taker = setup.taker.pubkey();
+    let token_b_mint = setup.token_b_mint;
+    setup.maker_token_b_ata =
+        setup_token_account(&mut setup.svm, &token_b_mint, &taker, &setup.maker)?;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::InvalidTokenOwner)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    setup.maker_token_b_ata = maker_token_b_ata;
+    setup.take_escrow()?;
+    setup.verify_simple_escrow_balances(1000, 2000, "after_take")?;
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_rejects_substituted_vault() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Another escrow-owned token A account holding the same amount
+    let escrow_pda = setup.escrow_pda;
+    let token_a_mint = setup.token_a_mint;
+    let decoy_vault =
+        setup_token_account(&mut setup.svm, &token_a_mint, &escrow_pda, &setup.maker)?;
+    mint_to(
+        &mut setup.svm,
+        &token_a_mint,
+        &setup.maker,
+        &decoy_vault,
+        1000,
+    )?;
+
+    let mut take = setup.take_instruction(vec![0x02], setup.program_id);
+    take.accounts[1] = AccountMeta::new(decoy_vault, false);
+    let taker = setup.taker.insecure_clone();
+    let result = setup.send_instructions(&[take], &[&taker]);
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+
+    assert_eq!(setup.get_token_account_balance(&decoy_vault), 1000);
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_with_token_2022_token_a() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Token A is an extension-free Token-2022 mint; token B stays on the classic program
+    let maker = setup.maker.pubkey();
+    let taker = setup.taker.pubkey();
+    let escrow_pda = setup.escrow_pda;
+    let token_a_mint = setup_token_2022_mint(&mut setup.svm, &maker, 9);
+    setup.token_a_mint = token_a_mint;
+    setup.maker_token_a_ata =
+        setup_token_2022_account(&mut setup.svm, &token_a_mint, &maker, 10000);
+    setup.escrow_token_a_ata =
+        setup_token_2022_account(&mut setup.svm, &token_a_mint, &escrow_pda, 0);
+    setup.taker_token_a_ata = setup_token_2022_account(&mut setup.svm, &token_a_mint, &taker, 0);
+
+    let mut make = setup.make_instruction(MakeEscrowIx::new(
+        EscrowType::Simple,
+        1000,
+        2000,
+        setup.bump,
+        setup.seed,
+    ));
+    make.accounts
+        .push(AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false));
+    let maker_keypair = setup.maker.insecure_clone();
+    setup.send_instructions(&[make], &[&maker_keypair])?;
+
+    let escrow = setup.get_escrow(&escrow_pda)?;
+    assert_eq!(escrow.token_program, TOKEN_2022_PROGRAM_ID.to_bytes());
+    assert_eq!(
+        setup.get_token_account_balance(&setup.escrow_token_a_ata),
+        1000
+    );
+
+    let take = setup.take_instruction(vec![0x02], TOKEN_2022_PROGRAM_ID);
+    let taker_keypair = setup.taker.insecure_clone();
+    setup.send_instructions(&[take], &[&taker_keypair])?;
+
+    assert_eq!(
+        setup.get_token_account_balance(&setup.maker_token_a_ata),
+        9000
+    );
+    assert_eq!(
+        setup.get_token_account_balance(&setup.taker_token_a_ata),
+        1000
+    );
+    assert_eq!(setup.get_maker_token_b_balance(), 12000);
+    assert_eq!(setup.get_taker_token_b_balance(), 8000);
+
+    // The drained vault and the escrow are closed through their own token program
+    assert!(setup
+        .svm
+        .get_account(&setup.escrow_token_a_ata)
+        .is_none_or(|a| a.data.is_empty()));
+    assert!(setup
+        .svm
+        .get_account(&escrow_pda)
+        .is_none_or(|a| a.data.is_empty()));
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_maker_cannot_take() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // The maker signs as the taker, paying and receiving with their own token accounts
+    let mut take = setup.take_instruction(vec![0x02], setup.program_id);
+    take.accounts[4] = AccountMeta::new(setup.maker.pubkey(), true);
+    take.accounts[5] = AccountMeta::new(setup.maker_token_a_ata, false);
+    take.accounts[6] = AccountMeta::new(setup.maker_token_b_ata, false);
+    let maker = setup.maker.insecure_clone();
+    let result = setup.send_instructions(&[take], &[&maker]);
+    assert_escrow_error(result, EscrowErrorCode::MakerCannotTake)?;
+
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_maker_token_a_balance(), 9000);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrow_take_reports_which_balance_is_short() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // The taker holds 10000 token B against a 20000 ask
+    setup.create_escrow(EscrowType::Simple, 1000, 20000)?;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::TakerInsufficientTokenB)?;
+
+    // The taker moves all but 500 of their token A away, below the 1000 on offer
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let taker = setup.taker.insecure_clone();
+    let spend = transfer(
+        &spl_token::ID,
+        &setup.taker_token_a_ata,
+        &setup.maker_token_a_ata,
+        &taker.pubkey(),
+        &[],
+        9500,
+    )?;
+    setup.send_instructions(&[spend], &[&taker])?;
+    let result = setup.take_escrow();
+    assert_escrow_error(result, EscrowErrorCode::TakerInsufficientTokenA)?;
+
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    Ok(())
+}
+
+#[test]
+fn test_simple_escrows_in_separate_namespaces() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let default_pda = setup.escrow_pda;
+
+    // The same maker and seed under the auction prefix derive another address
+    let prefix = ESCROW_PREFIXES[Escrow::NAMESPACE_AUCTION as usize];
+    let (auction_pda, bump) = Pubkey::find_program_address(
+        &[
+            prefix.as_bytes(),
+            setup.maker.pubkey().as_ref(),
+            &setup.seed,
+        ],
+        &setup.program_id,
+    );
+    assert_ne!(auction_pda, default_pda);
+
+    let token_a_mint = setup.token_a_mint;
+    setup.escrow_token_a_ata =
+        setup_ata(&mut setup.svm, &token_a_mint, &auction_pda, &setup.maker)?;
+    setup.escrow_pda = auction_pda;
+    setup.bump = bump;
+
+    // The address must match the namespace the make names
+    let ix = MakeEscrowIx::new(EscrowType::Simple, 500, 1000, bump, setup.seed);
+    let result = setup.create_escrow_with_ix(ix);
+    assert_escrow_error(result, EscrowErrorCode::PdaMismatch)?;
+
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        namespace: Escrow::NAMESPACE_AUCTION,
+        ..ix
+    })?;
+    assert_eq!(
+        setup.get_escrow(&auction_pda)?.namespace,
+        Escrow::NAMESPACE_AUCTION
+    );
+
+    // Takes sign for the escrow under its own prefix
+    setup.take_escrow()?;
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 500);
+    assert_eq!(setup.get_balance(&default_pda, &token_a_mint), 1000);
+    assert_eq!(
+        setup.get_escrow(&default_pda)?.namespace,
+        Escrow::NAMESPACE_ESCROW
+    );
+
+    Ok(())
+}
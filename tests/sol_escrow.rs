@@ -0,0 +1,85 @@
+use anyhow::Result;
+use escrow_suite::{instructions::MakeEscrowIx, states::EscrowType};
+use solana_sdk::{pubkey::Pubkey, signer::Signer, system_program};
+
+mod common;
+pub use common::*;
+
+/// Fee the taker pays for a transaction with one signature
+const TAKER_TX_FEE: u64 = 5000;
+
+fn lamports(setup: &EscrowTestSetup, address: &Pubkey) -> u64 {
+    setup.svm.get_balance(address).unwrap_or(0)
+}
+
+#[test]
+fn test_sol_escrow_partial_take_pays_lamports() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing SOL Escrow Partial Take ===");
+
+    // Token B is native SOL: 1000 token A for 2_000_000 lamports
+    setup.token_b_mint = system_program::ID;
+    setup.create_escrow(EscrowType::Partial, 1000, 2_000_000)?;
+
+    let maker = setup.maker.pubkey();
+    let taker = setup.taker.pubkey();
+    let maker_before = lamports(&setup, &maker);
+    let taker_before = lamports(&setup, &taker);
+
+    setup.take_partial_escrow(250)?;
+
+    assert_eq!(lamports(&setup, &maker), maker_before + 500_000);
+    assert_eq!(
+        lamports(&setup, &taker),
+        taker_before - 500_000 - TAKER_TX_FEE
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 250);
+    assert_eq!(setup.get_escrow_token_a_balance(), 750);
+
+    println!("✅ SOL escrow partial take test passed");
+    Ok(())
+}
+
+#[test]
+fn test_sol_escrow_full_take_pays_lamports_and_closes() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.token_b_mint = system_program::ID;
+    setup.create_escrow(EscrowType::Simple, 1000, 1_000_000)?;
+
+    let maker = setup.maker.pubkey();
+    let maker_before = lamports(&setup, &maker);
+    let escrow_rent =
+        lamports(&setup, &setup.escrow_pda) + lamports(&setup, &setup.escrow_token_a_ata);
+
+    setup.take_escrow()?;
+
+    // The asked lamports plus the rent of the closed escrow accounts
+    assert_eq!(
+        lamports(&setup, &maker),
+        maker_before + 1_000_000 + escrow_rent
+    );
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+    assert!(setup.svm.get_account(&setup.escrow_pda).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_sol_escrow_rejects_proceeds_vault() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.token_b_mint = system_program::ID;
+    let result = setup.create_escrow_with_ix(MakeEscrowIx {
+        token_b_vault: setup.maker_token_b_ata.to_bytes(),
+        ..MakeEscrowIx::new(EscrowType::Simple, 1000, 1_000_000, setup.bump, setup.seed)
+    });
+    assert_escrow_error(
+        result,
+        escrow_suite::error::EscrowErrorCode::InvalidProceedsVault,
+    )?;
+    assert_eq!(setup.get_maker_token_a_balance(), 10000);
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use anyhow::Result;
+use escrow_suite::{
+    instructions::{
+        BATCH_STATUS_INVALID, ESCROW_CHECK_INVALID_ESCROW, ESCROW_CHECK_VAULT_AUTHORITY,
+        ESCROW_CHECK_VAULT_BALANCE, ESCROW_CHECK_VAULT_MINT, ESCROW_CHECK_VAULT_PROGRAM,
+    },
+    states::{EscrowStatus, EscrowType},
+};
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_status_partial_escrow_lifecycle() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let total_token_a = 4000;
+    let total_token_b = 8000;
+
+    println!("=== Testing Status Across Partial Escrow Lifecycle ===");
+
+    // No escrow account exists yet
+    assert_eq!(setup.query_status()?, EscrowStatus::Closed as u8);
+
+    setup.create_escrow(EscrowType::Partial, total_token_a, total_token_b)?;
+    assert_eq!(setup.query_status()?, EscrowStatus::Open as u8);
+
+    setup.take_partial_escrow(1000)?;
+    assert_eq!(setup.query_status()?, EscrowStatus::PartiallyFilled as u8);
+
+    setup.take_partial_escrow(total_token_a - 1000)?;
+    assert_eq!(setup.query_status()?, EscrowStatus::Closed as u8);
+
+    println!("✅ Partial escrow status lifecycle test passed");
+    Ok(())
+}
+
+#[test]
+fn test_status_simple_escrow_after_take() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Status of Simple Escrow ===");
+
+    setup.create_escrow(EscrowType::Simple, 2000, 4000)?;
+    assert_eq!(setup.query_status()?, EscrowStatus::Open as u8);
+
+    setup.take_escrow()?;
+    assert_eq!(setup.query_status()?, EscrowStatus::Closed as u8);
+
+    println!("✅ Simple escrow status test passed");
+    Ok(())
+}
+
+#[test]
+fn test_batch_status_reads_several_escrows() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Batch Status ===");
+
+    setup.create_escrow(EscrowType::Partial, 3000, 6000)?;
+    setup.take_partial_escrow(1000)?;
+    let partially_filled = setup.escrow_pda;
+
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let open = setup.escrow_pda;
+
+    setup.use_seed(3u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    setup.take_escrow()?;
+    let taken = setup.escrow_pda;
+
+    // A token account isn't an escrow and gets flagged rather than failing the read
+    let not_an_escrow = setup.maker_token_a_ata;
+
+    let statuses = setup.query_batch_status(&[partially_filled, open, taken, not_an_escrow])?;
+    assert_eq!(
+        statuses,
+        vec![
+            (EscrowStatus::PartiallyFilled as u8, 2000),
+            (EscrowStatus::Open as u8, 1000),
+            (EscrowStatus::Closed as u8, 0),
+            (BATCH_STATUS_INVALID, 0),
+        ]
+    );
+
+    println!("✅ Batch status test passed");
+    Ok(())
+}
+
+#[test]
+fn test_status_reads_escrows_with_large_seeds() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    // Every seed byte is above the escrow type discriminators
+    setup.use_seed([0xEE; 8])?;
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    assert_eq!(
+        setup.query_batch_status(&[setup.escrow_pda])?,
+        vec![(EscrowStatus::Open as u8, 1000)]
+    );
+    assert_eq!(
+        setup.query_check_escrow(setup.escrow_pda, setup.escrow_token_a_ata)?,
+        0
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_check_escrow_flags_each_violation() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Check Escrow ===");
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let escrow = setup.escrow_pda;
+    assert_eq!(
+        setup.query_check_escrow(escrow, setup.escrow_token_a_ata)?,
+        0
+    );
+
+    // Copy the escrow's token account to a new address, edited by `edit`
+    let vault = setup.svm.get_account(&setup.escrow_token_a_ata).unwrap();
+    let mut copy_vault = |edit: &dyn Fn(&mut solana_sdk::account::Account)| {
+        let mut account = vault.clone();
+        edit(&mut account);
+        let address = Pubkey::new_unique();
+        setup.svm.set_account(address, account).unwrap();
+        address
+    };
+
+    let drained = copy_vault(&|account| {
+        let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+        token_account.amount = 999;
+        token_account.pack_into_slice(&mut account.data);
+    });
+    let wrong_program = copy_vault(&|account| account.owner = TOKEN_2022_PROGRAM_ID);
+
+    // The maker's own token A account is the right mint but the wrong authority
+    assert_eq!(
+        setup.query_check_escrow(escrow, setup.maker_token_a_ata)?,
+        ESCROW_CHECK_VAULT_AUTHORITY
+    );
+    assert_ne!(
+        setup.query_check_escrow(escrow, setup.maker_token_b_ata)? & ESCROW_CHECK_VAULT_MINT,
+        0
+    );
+    assert_eq!(
+        setup.query_check_escrow(escrow, drained)?,
+        ESCROW_CHECK_VAULT_BALANCE
+    );
+    assert_eq!(
+        setup.query_check_escrow(escrow, wrong_program)?,
+        ESCROW_CHECK_VAULT_PROGRAM
+    );
+
+    // A token account passed as the escrow isn't an escrow at all
+    assert_eq!(
+        setup.query_check_escrow(setup.maker_token_a_ata, setup.escrow_token_a_ata)?,
+        ESCROW_CHECK_INVALID_ESCROW
+    );
+
+    println!("✅ Check escrow test passed");
+    Ok(())
+}
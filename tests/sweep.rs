@@ -0,0 +1,50 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, states::EscrowType};
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_sweep_returns_stray_token_b_to_maker() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    // Token B sent straight to the escrow PDA's token B account
+    let escrow_token_b_ata = setup.setup_escrow_token_b_ata()?;
+    let token_b_mint = setup.token_b_mint;
+    mint_to(
+        &mut setup.svm,
+        &token_b_mint,
+        &setup.maker,
+        &escrow_token_b_ata,
+        37,
+    )?;
+
+    setup.sweep_escrow(escrow_token_b_ata)?;
+
+    assert_eq!(setup.get_token_account_balance(&escrow_token_b_ata), 0);
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 37);
+    // The offer itself is untouched
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    // Nothing left to sweep
+    let result = setup.sweep_escrow(escrow_token_b_ata);
+    assert_escrow_error(result, EscrowErrorCode::InsufficientFunds)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_sweep_rejects_accounts_the_escrow_does_not_own() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+
+    let taker_token_b_ata = setup.taker_token_b_ata;
+    let result = setup.sweep_escrow(taker_token_b_ata);
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowVault)?;
+    assert_eq!(setup.get_taker_token_b_balance(), 10000);
+
+    Ok(())
+}
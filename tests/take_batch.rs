@@ -0,0 +1,104 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::TakeBatchEntry, states::EscrowType};
+use solana_sdk::instruction::InstructionError;
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_take_batch_fills_several_partial_escrows() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Batch Take ===");
+
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    let first = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 3000)?;
+    let second = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    let escrows = [first, second];
+    let entry = |escrow_index: u8, token_a_amount: u64| TakeBatchEntry {
+        escrow_index,
+        token_a_amount,
+        max_token_b: u64::MAX,
+    };
+
+    // An entry pointing past the escrow groups fails the whole batch
+    let result = setup.take_batch(&escrows, &[entry(0, 1000), entry(2, 1000)]);
+    assert_instruction_error(result, InstructionError::NotEnoughAccountKeys)?;
+    assert_eq!(setup.get_token_account_balance(&first.1), 2000);
+
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_batch(&escrows, &[entry(0, 1000), entry(1, 500)])?;
+
+    assert_eq!(setup.get_token_account_balance(&first.1), 1000);
+    assert_eq!(setup.get_token_account_balance(&second.1), 1500);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1500);
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        2000 + 750
+    );
+
+    println!("✅ Batch take test passed");
+    Ok(())
+}
+
+#[test]
+fn test_take_batch_rejects_non_partial_escrows() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 2000)?;
+    let escrows = [(setup.escrow_pda, setup.escrow_token_a_ata)];
+
+    let result = setup.take_batch(
+        &escrows,
+        &[TakeBatchEntry {
+            escrow_index: 0,
+            token_a_amount: 1000,
+            max_token_b: u64::MAX,
+        }],
+    );
+    assert_escrow_error(result, EscrowErrorCode::InvalidEscrowType)?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_batch_rejects_entries_over_max_token_b() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Batch Take Max Token B ===");
+
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    let first = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 3000)?;
+    let second = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    let escrows = [first, second];
+    let entry = |escrow_index: u8, token_a_amount: u64, max_token_b: u64| TakeBatchEntry {
+        escrow_index,
+        token_a_amount,
+        max_token_b,
+    };
+
+    // 500 token A of the second escrow costs 750 token B, over the entry's limit of 749
+    let result = setup.take_batch(&escrows, &[entry(0, 1000, 2000), entry(1, 500, 749)]);
+    assert_escrow_error(result, EscrowErrorCode::LimitPriceExceeded)?;
+    assert_eq!(setup.get_token_account_balance(&first.1), 2000);
+    assert_eq!(setup.get_token_account_balance(&second.1), 2000);
+
+    // Limits at the exact cost go through
+    setup.take_batch(&escrows, &[entry(0, 1000, 2000), entry(1, 500, 750)])?;
+    assert_eq!(setup.get_token_account_balance(&first.1), 1000);
+    assert_eq!(setup.get_token_account_balance(&second.1), 1500);
+
+    println!("✅ Batch take max token B test passed");
+    Ok(())
+}
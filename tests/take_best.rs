@@ -0,0 +1,158 @@
+use anyhow::Result;
+use escrow_suite::{
+    error::EscrowErrorCode,
+    instructions::{MakeEscrowIx, SetConfigIx, TakeBestIx},
+    states::EscrowType,
+};
+use solana_sdk::pubkey::Pubkey;
+
+mod common;
+pub use common::*;
+
+#[test]
+fn test_take_best_fills_cheapest_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Take Best ===");
+
+    // Three offers for 1000 token A: 2000, 1500 and a Dutch auction currently at 1800
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    let expensive = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 3000)?;
+    let cheapest = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    setup.use_seed(3u64.to_le_bytes())?;
+    setup.create_dutch_auction_escrow(1000, 1800, 1000, 3600)?;
+    let auction = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    let candidates = [expensive, cheapest, auction];
+
+    // No offer is within a 1400 budget
+    let result = setup.take_best(
+        &candidates,
+        TakeBestIx {
+            token_a_amount: 1000,
+            max_token_b_amount: 1400,
+        },
+    );
+    assert_escrow_error(result, EscrowErrorCode::NoMatchingEscrow)?;
+
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_best(
+        &candidates,
+        TakeBestIx {
+            token_a_amount: 1000,
+            max_token_b_amount: 2000,
+        },
+    )?;
+
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        1500
+    );
+    assert_eq!(setup.get_token_account_balance(&cheapest.1), 1000);
+    assert_eq!(setup.get_token_account_balance(&expensive.1), 2000);
+    assert_eq!(setup.get_token_account_balance(&auction.1), 1000);
+
+    println!("✅ Take best test passed");
+    Ok(())
+}
+
+#[test]
+fn test_take_best_pays_protocol_fee_of_chosen_escrow() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    let treasury = Pubkey::new_unique();
+    let token_b_mint = setup.token_b_mint;
+    let fee_collector = setup_ata(&mut setup.svm, &token_b_mint, &treasury, &setup.maker)?;
+
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    let expensive = (
+        setup.escrow_pda,
+        setup.escrow_token_a_ata,
+        setup.maker_token_b_ata,
+    );
+
+    // The cheaper offer charges a 1% fee to the treasury
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow_with_ix(MakeEscrowIx {
+        fee_bps: 100,
+        fee_recipient: treasury.to_bytes(),
+        ..MakeEscrowIx::new(EscrowType::Partial, 2000, 3000, setup.bump, setup.seed)
+    })?;
+    let cheapest = (setup.escrow_pda, setup.escrow_token_a_ata, fee_collector);
+
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_best_with_fee_collectors(
+        &[expensive, cheapest],
+        TakeBestIx {
+            token_a_amount: 1000,
+            max_token_b_amount: 2000,
+        },
+    )?;
+
+    assert_eq!(setup.get_token_account_balance(&fee_collector), 15);
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        1485
+    );
+    assert_eq!(setup.get_token_account_balance(&cheapest.1), 1000);
+    assert_eq!(setup.get_token_account_balance(&expensive.1), 2000);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_best_skips_escrows_take_would_reject() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Take Best Skips Untakeable Escrows ===");
+
+    setup.use_seed(1u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 4000)?;
+    let expensive = (setup.escrow_pda, setup.escrow_token_a_ata);
+
+    // The cheapest offer has its vault frozen and the next one has fills disabled
+    setup.use_seed(2u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 2000)?;
+    let frozen = (setup.escrow_pda, setup.escrow_token_a_ata);
+    setup.freeze_token_account(&frozen.1)?;
+
+    setup.use_seed(3u64.to_le_bytes())?;
+    setup.create_escrow(EscrowType::Partial, 2000, 3000)?;
+    let disabled = (setup.escrow_pda, setup.escrow_token_a_ata);
+    setup.set_fills_enabled(false)?;
+
+    let candidates = [expensive, frozen, disabled];
+    let ix = TakeBestIx {
+        token_a_amount: 1000,
+        max_token_b_amount: 2000,
+    };
+
+    // Paused takes fail outright rather than picking a candidate
+    setup.set_config(SetConfigIx {
+        takes_paused: true,
+        ..SetConfigIx::default()
+    })?;
+    let result = setup.take_best(&candidates, ix);
+    assert_escrow_error(result, EscrowErrorCode::ProgramPaused)?;
+    setup.set_config(SetConfigIx::default())?;
+
+    let maker_token_b_before = setup.get_maker_token_b_balance();
+    setup.take_best(&candidates, ix)?;
+
+    assert_eq!(
+        setup.get_maker_token_b_balance() - maker_token_b_before,
+        2000
+    );
+    assert_eq!(setup.get_token_account_balance(&expensive.1), 1000);
+    assert_eq!(setup.get_token_account_balance(&frozen.1), 2000);
+    assert_eq!(setup.get_token_account_balance(&disabled.1), 2000);
+
+    println!("✅ Take best skip test passed");
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use escrow_suite::{error::EscrowErrorCode, instructions::UpdateEscrowIx, states::EscrowType};
+
+mod common;
+pub use common::*;
+
+fn new_ask(token_b_amount: u64) -> UpdateEscrowIx {
+    UpdateEscrowIx {
+        token_b_amount,
+        end_price: 0,
+        duration: 0,
+    }
+}
+
+#[test]
+fn test_update_simple_escrow_ask_then_take() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Update Simple Escrow ===");
+
+    setup.create_escrow(EscrowType::Simple, 1000, 4000)?;
+
+    // The maker lowers the ask instead of cancelling and recreating
+    setup.update_escrow(new_ask(2500))?;
+    assert_eq!(setup.get_escrow_token_a_balance(), 1000);
+
+    setup.take_escrow()?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 2500);
+    assert_eq!(setup.get_taker_token_b_balance(), 10000 - 2500);
+    assert_eq!(setup.get_taker_token_a_balance(), 10000 + 1000);
+
+    println!("✅ Update simple escrow test passed");
+    Ok(())
+}
+
+#[test]
+fn test_update_rejects_free_ask() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    setup.create_escrow(EscrowType::Simple, 1000, 4000)?;
+
+    let result = setup.update_escrow(new_ask(0));
+    assert_escrow_error(result, EscrowErrorCode::InvalidPriceRange)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_update_dutch_auction_schedule() -> Result<()> {
+    let mut setup = EscrowTestSetup::new()?;
+
+    println!("=== Testing Update Dutch Auction ===");
+
+    setup.set_time(1_000_000)?;
+    setup.create_dutch_auction_escrow(1000, 8000, 4000, 1000)?;
+
+    // An end price above the start price is rejected
+    let result = setup.update_escrow(UpdateEscrowIx {
+        token_b_amount: 6000,
+        end_price: 7000,
+        duration: 1000,
+    });
+    assert_escrow_error(result, EscrowErrorCode::InvalidAuctionParams)?;
+
+    // Decay from 6000 to 2000 over the original start time plus 2000 seconds
+    setup.update_escrow(UpdateEscrowIx {
+        token_b_amount: 6000,
+        end_price: 2000,
+        duration: 2000,
+    })?;
+
+    // Halfway through the new schedule the price is 4000
+    setup.advance_time(1000)?;
+    let result = setup.take_escrow_with_amounts(1000, 3999);
+    assert_escrow_error(result, EscrowErrorCode::DutchUnderpayment)?;
+    setup.take_escrow_with_amounts(1000, 4000)?;
+    assert_eq!(setup.get_maker_token_b_balance(), 10000 + 4000);
+
+    println!("✅ Update Dutch auction test passed");
+    Ok(())
+}